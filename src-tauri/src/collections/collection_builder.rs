@@ -1,6 +1,8 @@
 #[cfg(test)]
 use crate::collections::collections::{Collection, Style, Video};
 #[cfg(test)]
+use chrono::{DateTime, Utc};
+#[cfg(test)]
 use fake::faker::filesystem::fr_fr::FilePath;
 #[cfg(test)]
 use fake::faker::job::fr_fr::Title;
@@ -42,6 +44,10 @@ impl CollectionBuilder {
         Collection {
             id: uuid::Uuid::new_v4(),
             title: self.title.clone(),
+            description: None,
+            cover: None,
+            created_at: None,
+            updated_at: None,
             videos: self.videos.clone(),
         }
     }
@@ -58,6 +64,7 @@ pub struct VideoBuilder {
     thumbnail: String,
     size_bytes: u64,
     duration_seconds: u64,
+    added_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -73,11 +80,53 @@ impl VideoBuilder {
             thumbnail: FilePath().fake(),
             size_bytes: Faker.fake::<u64>(),
             duration_seconds: Faker.fake::<u64>(),
+            added_at: None,
         }
     }
 
+    pub fn with_size_bytes(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    pub fn with_duration_seconds(mut self, duration_seconds: u64) -> Self {
+        self.duration_seconds = duration_seconds;
+        self
+    }
+
+    pub fn with_added_at(mut self, added_at: DateTime<Utc>) -> Self {
+        self.added_at = Some(added_at);
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_artist(mut self, artist: &str) -> Self {
+        self.artist = artist.to_string();
+        self
+    }
+
+    pub fn with_song(mut self, song: &str) -> Self {
+        self.song = song.to_string();
+        self
+    }
+
+    pub fn with_style(mut self, style: Vec<Style>) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<&str>) -> Self {
+        self.tags = tags.into_iter().map(String::from).collect();
+        self
+    }
+
     pub fn build(self) -> Video {
         Video {
+            id: uuid::Uuid::new_v4(),
             path: PathBuf::from(self.path),
             name: self.name.clone(),
             artist: self.artist.clone(),
@@ -87,6 +136,7 @@ impl VideoBuilder {
             thumbnail: self.thumbnail.clone(),
             size_bytes: self.size_bytes,
             duration_seconds: self.duration_seconds,
+            added_at: self.added_at,
         }
     }
 }
@@ -1,8 +1,14 @@
 use crate::clock::clock;
-use crate::collections::events::{CollectionCreated, VideoAddedToCollection};
+use crate::collections::events::{
+    CollectionCreated, CollectionDeleted, CollectionUpdated, VideoAddedToCollection,
+    VideoRemovedFromCollection,
+};
+use crate::collections::trash::{TrashItem, Trashed};
 use crate::collections::video::VideoCollectionToUpdate;
+use crate::error::AppError;
 use crate::event_bus::EventBusManager;
 use crate::repositories::repositories;
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -32,8 +38,45 @@ pub enum Style {
     NeoClassical,
 }
 
+impl Style {
+    /// Name shown to users, the same as the serialized one.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Style::Rock => "Rock",
+            Style::HardRock => "Hard Rock",
+            Style::Metal => "Metal",
+            Style::Blues => "Blues",
+            Style::Jazz => "Jazz",
+            Style::Funk => "Funk",
+            Style::Pop => "Pop",
+            Style::CountryFolk => "Country / Folk",
+            Style::ReggaeSka => "Reggae / Ska",
+            Style::AmbientPostRock => "Ambient / Post-Rock",
+            Style::NeoClassical => "Neo-Classical",
+        }
+    }
+
+    /// Other spellings users search the style with.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Style::HardRock => &["hardrock"],
+            Style::Metal => &["heavy metal"],
+            Style::AmbientPostRock => &["postrock"],
+            Style::NeoClassical => &["neoclassical", "classical"],
+            Style::Rock
+            | Style::Blues
+            | Style::Jazz
+            | Style::Funk
+            | Style::Pop
+            | Style::CountryFolk
+            | Style::ReggaeSka => &[],
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Video {
+    pub id: Uuid,
     pub path: PathBuf,
     pub name: String,
     pub artist: String,
@@ -43,6 +86,8 @@ pub struct Video {
     pub thumbnail: String,
     pub size_bytes: u64,
     pub duration_seconds: u64,
+    #[serde(default)]
+    pub added_at: Option<DateTime<Utc>>,
 }
 
 impl Video {
@@ -53,6 +98,7 @@ impl Video {
             .unwrap_or("unknown")
             .to_string();
         Self {
+            id: Uuid::new_v4(),
             path,
             name,
             artist: "".to_string(),
@@ -62,6 +108,26 @@ impl Video {
             thumbnail,
             size_bytes,
             duration_seconds,
+            added_at: Some(clock().now()),
+        }
+    }
+
+    pub fn merge_metadata(&mut self, other: &Video) {
+        if self.artist.is_empty() {
+            self.artist = other.artist.clone();
+        }
+        if self.song.is_empty() {
+            self.song = other.song.clone();
+        }
+        for style in &other.style {
+            if !self.style.contains(style) {
+                self.style.push(style.clone());
+            }
+        }
+        for tag in &other.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
         }
     }
 }
@@ -70,14 +136,27 @@ impl Video {
 pub struct Collection {
     pub id: Uuid,
     pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub cover: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
     pub videos: Vec<Video>,
 }
 
 impl Collection {
-    fn new(uuid: Uuid, title: &str) -> Self {
+    pub(crate) fn new(uuid: Uuid, title: &str) -> Self {
+        let now = clock().now();
         Collection {
             id: uuid,
             title: title.to_string(),
+            description: None,
+            cover: None,
+            created_at: Some(now),
+            updated_at: Some(now),
             videos: vec![],
         }
     }
@@ -85,12 +164,20 @@ impl Collection {
     pub fn add_video(&mut self, video: Video) {
         self.videos.push(video);
     }
+
+    pub fn update(&mut self, collection_to_update: CollectionToUpdate) {
+        self.title = collection_to_update.title;
+        self.description = collection_to_update.description;
+        self.cover = collection_to_update.cover;
+        self.updated_at = Some(clock().now());
+    }
 }
 
 pub trait CollectionRepository: Send + Sync {
     fn list(&self) -> Vec<Collection>;
-    fn add(&self, c: Collection);
+    fn add(&self, c: Collection) -> Result<(), AppError>;
     fn get_by_id(&self, id: &Uuid) -> Option<Collection>;
+    fn delete(&self, id: &Uuid) -> Result<(), AppError>;
 }
 
 #[derive(Default)]
@@ -108,48 +195,285 @@ impl CollectionRepository for CollectionRepositoryMemory {
     fn list(&self) -> Vec<Collection> {
         self.items.lock().clone()
     }
-    fn add(&self, c: Collection) {
+    fn add(&self, c: Collection) -> Result<(), AppError> {
         if let Some(item) = self.items.lock().iter_mut().find(|x| x.id == c.id) {
             *item = c;
-            return;
+            return Ok(());
         }
         self.items.lock().push(c);
+        Ok(())
     }
 
     fn get_by_id(&self, id: &Uuid) -> Option<Collection> {
         self.items.lock().iter().find(|c| c.id == *id).cloned()
     }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        self.items.lock().retain(|c| c.id != *id);
+        Ok(())
+    }
 }
 
 pub struct CollectionService {}
 
 impl CollectionService {
+    pub(crate) fn find_collection(collection_id: &Uuid) -> Result<Collection, AppError> {
+        repositories()
+            .collections()
+            .get_by_id(collection_id)
+            .ok_or(AppError::not_found("Collection", collection_id))
+    }
+
     pub(crate) fn update_video(
         video_to_update: VideoCollectionToUpdate,
-        _bus_manager: EventBusManager,
-    ) {
-        let mut collection = repositories()
-            .collections()
-            .get_by_id(&video_to_update.collection_id)
-            .unwrap();
+        bus_manager: EventBusManager,
+    ) -> Result<(), AppError> {
+        let mut collection = Self::find_collection(&video_to_update.collection_id)?;
 
-        if let Some(video) = collection
+        let video = collection
             .videos
             .iter_mut()
-            .find(|p| p.path == video_to_update.video.path)
-        {
-            video.style = video_to_update.video.style.clone();
-            video.name = video_to_update.video.name.clone();
-            video.artist = video_to_update.video.artist.clone();
-            video.song = video_to_update.video.song.clone();
-            video.tags = video_to_update.video.tags.clone();
-            video.thumbnail = video_to_update.video.thumbnail.clone();
-            video.size_bytes = video_to_update.video.size_bytes;
+            .find(|v| v.id == video_to_update.video.id)
+            .ok_or(AppError::not_found("Video", video_to_update.video.id))?;
+        video.style = video_to_update.video.style.clone();
+        video.name = video_to_update.video.name.clone();
+        video.artist = video_to_update.video.artist.clone();
+        video.song = video_to_update.video.song.clone();
+        video.tags = video_to_update.video.tags.clone();
+        video.thumbnail = video_to_update.video.thumbnail.clone();
+        video.size_bytes = video_to_update.video.size_bytes;
+        let updated = VideoAddedToCollection::from((&*video, collection.id));
+        repositories().collections().add(collection)?;
+        bus_manager.publish("video:updated", updated);
+        Ok(())
+    }
+
+    pub fn update_collection(
+        collection_to_update: CollectionToUpdate,
+        bus_manager: EventBusManager,
+    ) -> Result<Collection, AppError> {
+        let mut collection = Self::find_collection(&collection_to_update.id)?;
+
+        collection.update(collection_to_update);
+        repositories().collections().add(collection.clone())?;
+        bus_manager.publish("collection:updated", CollectionUpdated::from(&collection));
+        Ok(collection)
+    }
+}
+
+impl CollectionService {
+    pub fn delete_collection(
+        collection_id: Uuid,
+        bus_manager: EventBusManager,
+    ) -> Result<(), AppError> {
+        let collection = Self::find_collection(&collection_id)?;
+
+        repositories()
+            .trash()
+            .add(TrashItem::new(Trashed::Collection { collection }))?;
+        repositories().collections().delete(&collection_id)?;
+        bus_manager.publish("collection:deleted", CollectionDeleted { collection_id });
+        Ok(())
+    }
+
+    pub fn remove_video(
+        collection_id: Uuid,
+        video_id: Uuid,
+        bus_manager: EventBusManager,
+    ) -> Result<(), AppError> {
+        let mut collection = Self::find_collection(&collection_id)?;
+        let position = collection
+            .videos
+            .iter()
+            .position(|v| v.id == video_id)
+            .ok_or(AppError::not_found("Video", video_id))?;
+
+        let video = collection.videos.remove(position);
+        repositories().trash().add(TrashItem::new(Trashed::Video {
+            collection_id,
+            video: video.clone(),
+        }))?;
+        repositories().collections().add(collection)?;
+        bus_manager.publish(
+            "video:removed",
+            VideoRemovedFromCollection::from((&video, collection_id)),
+        );
+        Ok(())
+    }
+}
+
+impl CollectionService {
+    pub fn move_videos(
+        from_collection_id: Uuid,
+        to_collection_id: Uuid,
+        video_ids: Vec<Uuid>,
+        bus_manager: EventBusManager,
+    ) -> Result<(), AppError> {
+        let (mut from, mut to) = Self::get_both(from_collection_id, to_collection_id)?;
+
+        let mut moved = Vec::new();
+        for video_id in video_ids {
+            let Some(position) = from.videos.iter().position(|v| v.id == video_id) else {
+                continue;
+            };
+            let video = from.videos.remove(position);
+            let transferred = Self::transfer_video(&video, &mut to);
+            moved.push((video, transferred));
+        }
+        Self::save_both(&mut from, &mut to)?;
+        for (video, transferred) in moved {
+            bus_manager.publish(
+                "video:removed",
+                VideoRemovedFromCollection::from((&video, from.id)),
+            );
+            transferred.publish(to.id, &bus_manager);
+        }
+        bus_manager.publish("collection:updated", CollectionUpdated::from(&from));
+        bus_manager.publish("collection:updated", CollectionUpdated::from(&to));
+        Ok(())
+    }
+
+    pub fn copy_videos(
+        from_collection_id: Uuid,
+        to_collection_id: Uuid,
+        video_ids: Vec<Uuid>,
+        bus_manager: EventBusManager,
+    ) -> Result<(), AppError> {
+        let (from, mut to) = Self::get_both(from_collection_id, to_collection_id)?;
+
+        let transferred: Vec<Transferred> = from
+            .videos
+            .iter()
+            .filter(|v| video_ids.contains(&v.id))
+            .map(|video| {
+                let copy = Video {
+                    id: Uuid::new_v4(),
+                    ..video.clone()
+                };
+                Self::transfer_video(&copy, &mut to)
+            })
+            .collect();
+        to.updated_at = Some(clock().now());
+        repositories().collections().add(to.clone())?;
+        transferred
+            .iter()
+            .for_each(|t| t.publish(to.id, &bus_manager));
+        bus_manager.publish("collection:updated", CollectionUpdated::from(&to));
+        Ok(())
+    }
+
+    pub fn merge_collections(
+        source_collection_id: Uuid,
+        target_collection_id: Uuid,
+        bus_manager: EventBusManager,
+    ) -> Result<Collection, AppError> {
+        let (source, mut target) = Self::get_both(source_collection_id, target_collection_id)?;
+
+        let transferred: Vec<Transferred> = source
+            .videos
+            .iter()
+            .map(|video| Self::transfer_video(video, &mut target))
+            .collect();
+        target.updated_at = Some(clock().now());
+        repositories().collections().add(target.clone())?;
+        repositories()
+            .trash()
+            .add(TrashItem::new(Trashed::Collection {
+                collection: source.clone(),
+            }))?;
+        repositories().collections().delete(&source.id)?;
+        transferred
+            .iter()
+            .for_each(|t| t.publish(target.id, &bus_manager));
+        bus_manager.publish("collection:updated", CollectionUpdated::from(&target));
+        bus_manager.publish(
+            "collection:deleted",
+            CollectionDeleted {
+                collection_id: source.id,
+            },
+        );
+        Ok(target)
+    }
+
+    fn get_both(
+        first_collection_id: Uuid,
+        second_collection_id: Uuid,
+    ) -> Result<(Collection, Collection), AppError> {
+        if first_collection_id == second_collection_id {
+            return Err(AppError::invalid_input(
+                "Source and target collections must be different",
+            ));
+        }
+        let first = Self::find_collection(&first_collection_id)?;
+        let second = Self::find_collection(&second_collection_id)?;
+        Ok((first, second))
+    }
+
+    fn save_both(first: &mut Collection, second: &mut Collection) -> Result<(), AppError> {
+        let now = clock().now();
+        first.updated_at = Some(now);
+        second.updated_at = Some(now);
+        repositories().collections().add(first.clone())?;
+        repositories().collections().add(second.clone())
+    }
+
+    /// Adds `video` to `to`, or merges its metadata into the video `to`
+    /// already holds at the same path.
+    fn transfer_video(video: &Video, to: &mut Collection) -> Transferred {
+        match to.videos.iter_mut().find(|v| v.path == video.path) {
+            Some(existing) => {
+                existing.merge_metadata(video);
+                Transferred::Merged(existing.clone())
+            }
+            None => {
+                to.add_video(video.clone());
+                Transferred::Added(video.clone())
+            }
+        }
+    }
+
+    /// Published once the collection is saved: the search index reads the
+    /// collection back from the repository.
+    fn publish_added(videos: &[Video], collection_id: Uuid, bus_manager: &EventBusManager) {
+        for video in videos {
+            bus_manager.publish(
+                "video:added",
+                VideoAddedToCollection::from((video, collection_id)),
+            );
         }
-        repositories().collections().add(collection);
     }
 }
 
+/// Outcome of transferring a video, published once the target is saved.
+enum Transferred {
+    Added(Video),
+    /// The target already held the video and took over its metadata.
+    Merged(Video),
+}
+
+impl Transferred {
+    fn publish(&self, collection_id: Uuid, bus_manager: &EventBusManager) {
+        match self {
+            Transferred::Added(video) => bus_manager.publish(
+                "video:added",
+                VideoAddedToCollection::from((video, collection_id)),
+            ),
+            Transferred::Merged(video) => bus_manager.publish(
+                "video:updated",
+                VideoAddedToCollection::from((video, collection_id)),
+            ),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct CollectionToUpdate {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub cover: Option<String>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct VideoToAdd {
     pub path: PathBuf,
@@ -162,30 +486,50 @@ impl CollectionService {
     pub fn create_collection(
         videos_paths: Vec<VideoToAdd>,
         bus_manager: EventBusManager,
-    ) -> Collection {
+    ) -> Result<Collection, AppError> {
         let mut collection = Collection::new(
             Uuid::new_v4(),
             format!("Collection - {}", clock().now().format("%Y-%m-%d")).as_str(),
         );
+
+        videos_paths.into_iter().for_each(|v| {
+            let video = Video::new(v.path, v.thumbnail, v.size_bytes, v.duration_seconds);
+            collection.add_video(video);
+        });
+        repositories().collections().add(collection.clone())?;
         bus_manager.publish("collection:created", CollectionCreated::from(&collection));
+        Self::publish_added(&collection.videos, collection.id, &bus_manager);
+        Ok(collection)
+    }
+
+    pub fn add_videos_to_collection(
+        collection_id: Uuid,
+        videos_paths: Vec<VideoToAdd>,
+        bus_manager: EventBusManager,
+    ) -> Result<Collection, AppError> {
+        let mut collection = Self::find_collection(&collection_id)?;
 
+        let mut added = Vec::new();
         videos_paths.into_iter().for_each(|v| {
+            if collection.videos.iter().any(|video| video.path == v.path) {
+                return;
+            }
             let video = Video::new(v.path, v.thumbnail, v.size_bytes, v.duration_seconds);
             collection.add_video(video.clone());
-            bus_manager.publish(
-                "video:added",
-                VideoAddedToCollection::from((&video, collection.id)),
-            );
+            added.push(video);
         });
-        repositories().collections().add(collection.clone());
-        collection
+        collection.updated_at = Some(clock().now());
+        repositories().collections().add(collection.clone())?;
+        Self::publish_added(&added, collection.id, &bus_manager);
+        Ok(collection)
     }
 }
 
 #[cfg(test)]
-mod collection_service_setup {
+pub(crate) mod collection_service_setup {
     use crate::clock::ClockGuard;
     use crate::collections::collections::CollectionRepositoryMemory;
+    use crate::collections::trash::TrashRepositoryMemory;
     use crate::infra::event_bus::memory_event_bus::MemoryEventBus;
     use crate::repositories::{with_test_repositories, Repositories, RepositoriesGuard};
     use chrono::{DateTime, MappedLocalTime, Utc};
@@ -197,7 +541,10 @@ mod collection_service_setup {
         let now = current_date_time.unwrap();
         let _clock_guard = crate::clock::with_static_clock(now);
         let mem = CollectionRepositoryMemory::new();
-        let _repos_guard = with_test_repositories(Repositories::new(Arc::new(mem)));
+        let _repos_guard = with_test_repositories(Repositories::new(
+            Arc::new(mem),
+            Arc::new(TrashRepositoryMemory::new()),
+        ));
         let event_bus = Arc::new(MemoryEventBus::new());
 
         (event_bus, _clock_guard, _repos_guard)
@@ -227,7 +574,8 @@ mod collection_service_create_collection_tests {
                 duration_seconds: 123,
             }],
             EventBusManager::new(event_bus.clone()),
-        );
+        )
+        .unwrap();
 
         assert_eq!(repositories().collections().list().len(), 1);
         assert_eq!(
@@ -235,7 +583,12 @@ mod collection_service_create_collection_tests {
             Some(Collection {
                 id: collection.id,
                 title: "Collection - 2026-01-28".to_string(),
+                description: None,
+                cover: None,
+                created_at: Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap()),
+                updated_at: Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap()),
                 videos: vec![Video {
+                    id: collection.videos[0].id,
                     path: PathBuf::from("foo/video.mp4"),
                     name: "video.mp4".to_string(),
                     artist: "".to_string(),
@@ -244,7 +597,8 @@ mod collection_service_create_collection_tests {
                     tags: vec![],
                     thumbnail: "thumbnail".to_string(),
                     size_bytes: 1_200_300,
-                    duration_seconds: 123
+                    duration_seconds: 123,
+                    added_at: Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap()),
                 }]
             })
         );
@@ -263,7 +617,8 @@ mod collection_service_create_collection_tests {
                 duration_seconds: 123,
             }],
             EventBusManager::new(event_bus.clone()),
-        );
+        )
+        .unwrap();
 
         let events = event_bus.events.lock();
         assert_eq!(events.len(), 2);
@@ -292,7 +647,8 @@ mod collection_service_create_collection_tests {
                 duration_seconds: 0,
             }],
             EventBusManager::new(event_bus.clone()),
-        );
+        )
+        .unwrap();
 
         let events = event_bus.events.lock();
         assert_eq!(events.len(), 2);
@@ -301,6 +657,7 @@ mod collection_service_create_collection_tests {
             events[1].data,
             serde_json::to_value(VideoAddedToCollection {
                 collection_id: collection.id,
+                id: collection.videos[0].id,
                 path: "foo/video.mp4".parse().unwrap(),
                 name: "video.mp4".to_string(),
                 artist: "".to_string(),
@@ -310,18 +667,106 @@ mod collection_service_create_collection_tests {
                 thumbnail: "".to_string(),
                 size_bytes: 0,
                 duration_seconds: 0,
+                added_at: collection.videos[0].added_at,
             })
             .unwrap()
         );
     }
 }
 
+#[cfg(test)]
+mod collection_service_add_videos_to_collection_tests {
+    use crate::collections::collections::collection_service_setup::setup;
+    use crate::collections::collections::{CollectionService, VideoToAdd};
+    use crate::collections::events::VideoAddedToCollection;
+    use crate::event_bus::EventBusManager;
+    use crate::repositories::repositories;
+    use chrono::{TimeZone, Utc};
+
+    fn video_to_add(path: &str) -> VideoToAdd {
+        VideoToAdd {
+            path: path.parse().unwrap(),
+            thumbnail: "".to_string(),
+            size_bytes: 0,
+            duration_seconds: 0,
+        }
+    }
+
+    #[test]
+    fn add_videos_to_existing_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection = CollectionService::create_collection(
+            vec![video_to_add("lessons/week-1.mp4")],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        CollectionService::add_videos_to_collection(
+            collection.id,
+            vec![video_to_add("lessons/week-2.mp4")],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let retrieved_collection = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert_eq!(retrieved_collection.videos.len(), 2);
+        assert_eq!(
+            retrieved_collection.videos[1].path.to_str(),
+            Some("lessons/week-2.mp4")
+        );
+        let events = event_bus.events.lock();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].event_type, "video:added");
+        assert_eq!(
+            events[2].data,
+            serde_json::to_value(VideoAddedToCollection::from((
+                &retrieved_collection.videos[1],
+                collection.id
+            )))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn skip_videos_already_in_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection = CollectionService::create_collection(
+            vec![video_to_add("lessons/week-1.mp4")],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        CollectionService::add_videos_to_collection(
+            collection.id,
+            vec![
+                video_to_add("lessons/week-1.mp4"),
+                video_to_add("lessons/week-2.mp4"),
+            ],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let retrieved_collection = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert_eq!(retrieved_collection.videos.len(), 2);
+        assert_eq!(event_bus.events.lock().len(), 3);
+    }
+}
+
 #[cfg(test)]
 mod collection_service_update_video_tests {
     use crate::collections::collections::collection_service_setup::setup;
     use crate::collections::collections::Style::Rock;
     use crate::collections::collections::{CollectionService, Video, VideoToAdd};
     use crate::collections::video::{VideoCollectionToUpdate, VideoToUpdate};
+    use crate::error::AppError;
     use crate::event_bus::EventBusManager;
     use crate::repositories::repositories;
     use chrono::{TimeZone, Utc};
@@ -338,13 +783,15 @@ mod collection_service_update_video_tests {
                 duration_seconds: 0,
             }],
             EventBusManager::new(event_bus.clone()),
-        );
+        )
+        .unwrap();
         let video = collection.videos[0].clone();
 
         CollectionService::update_video(
             VideoCollectionToUpdate {
                 collection_id: collection.id,
                 video: VideoToUpdate {
+                    id: video.id,
                     path: video.path.clone(),
                     name: "Rest my chemistry - Intro".to_string(),
                     artist: "Interpol".to_string(),
@@ -357,7 +804,8 @@ mod collection_service_update_video_tests {
                 },
             },
             EventBusManager::new(event_bus.clone()),
-        );
+        )
+        .unwrap();
 
         let retrieved_collection = repositories()
             .collections()
@@ -366,6 +814,7 @@ mod collection_service_update_video_tests {
         assert_eq!(
             retrieved_collection.videos[0],
             Video {
+                id: video.id,
                 path: video.path,
                 name: "Rest my chemistry - Intro".to_string(),
                 artist: "Interpol".to_string(),
@@ -375,7 +824,415 @@ mod collection_service_update_video_tests {
                 thumbnail: video.thumbnail,
                 size_bytes: video.size_bytes,
                 duration_seconds: video.duration_seconds,
+                added_at: video.added_at,
+            }
+        )
+    }
+
+    #[test]
+    fn update_video_of_unknown_collection_fails_with_not_found() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection_id = uuid::Uuid::new_v4();
+
+        let result = CollectionService::update_video(
+            VideoCollectionToUpdate {
+                collection_id,
+                video: VideoToUpdate {
+                    id: uuid::Uuid::new_v4(),
+                    path: "foo/video.mp4".parse().unwrap(),
+                    name: "".to_string(),
+                    artist: "".to_string(),
+                    song: "".to_string(),
+                    style: vec![],
+                    tags: vec![],
+                    thumbnail: "".to_string(),
+                    size_bytes: 0,
+                    duration_seconds: 0,
+                },
+            },
+            EventBusManager::new(event_bus.clone()),
+        );
+
+        assert_eq!(
+            result,
+            Err(AppError::not_found("Collection", collection_id))
+        );
+    }
+}
+
+#[cfg(test)]
+mod collection_service_update_collection_tests {
+    use crate::collections::collections::collection_service_setup::setup;
+    use crate::collections::collections::{CollectionService, CollectionToUpdate};
+    use crate::collections::events::CollectionUpdated;
+    use crate::event_bus::EventBusManager;
+    use crate::repositories::repositories;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn update_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection =
+            CollectionService::create_collection(vec![], EventBusManager::new(event_bus.clone()))
+                .unwrap();
+
+        CollectionService::update_collection(
+            CollectionToUpdate {
+                id: collection.id,
+                title: "Lessons with John".to_string(),
+                description: Some("Weekly guitar lessons".to_string()),
+                cover: Some("data:image/jpeg;base64,cover".to_string()),
+            },
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let retrieved_collection = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert_eq!(retrieved_collection.title, "Lessons with John");
+        assert_eq!(
+            retrieved_collection.description,
+            Some("Weekly guitar lessons".to_string())
+        );
+        assert_eq!(
+            retrieved_collection.cover,
+            Some("data:image/jpeg;base64,cover".to_string())
+        );
+        assert_eq!(
+            retrieved_collection.updated_at,
+            Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn update_collection_publishes_collection_updated() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection =
+            CollectionService::create_collection(vec![], EventBusManager::new(event_bus.clone()))
+                .unwrap();
+
+        CollectionService::update_collection(
+            CollectionToUpdate {
+                id: collection.id,
+                title: "Lessons with John".to_string(),
+                description: None,
+                cover: None,
+            },
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let events = event_bus.events.lock();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_type, "collection:updated");
+        assert_eq!(
+            events[1].data,
+            serde_json::to_value(CollectionUpdated {
+                collection_id: collection.id,
+                title: "Lessons with John".to_string(),
+                description: None,
+                cover: None,
+                updated_at: Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap()),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn update_unknown_collection_fails() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+
+        let result = CollectionService::update_collection(
+            CollectionToUpdate {
+                id: uuid::Uuid::new_v4(),
+                title: "Lessons with John".to_string(),
+                description: None,
+                cover: None,
+            },
+            EventBusManager::new(event_bus.clone()),
+        );
+
+        assert!(result.is_err());
+        assert!(event_bus.events.lock().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod collection_service_delete_tests {
+    use crate::collections::collections::collection_service_setup::setup;
+    use crate::collections::collections::{CollectionService, VideoToAdd};
+    use crate::collections::events::{CollectionDeleted, VideoRemovedFromCollection};
+    use crate::collections::trash::Trashed;
+    use crate::event_bus::EventBusManager;
+    use crate::repositories::repositories;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn delete_collection_moves_it_to_trash() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection =
+            CollectionService::create_collection(vec![], EventBusManager::new(event_bus.clone()))
+                .unwrap();
+
+        CollectionService::delete_collection(
+            collection.id,
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        assert!(repositories().collections().list().is_empty());
+        let trash = repositories().trash().list();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].content, Trashed::Collection { collection });
+    }
+
+    #[test]
+    fn delete_collection_publishes_collection_deleted() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection =
+            CollectionService::create_collection(vec![], EventBusManager::new(event_bus.clone()))
+                .unwrap();
+
+        CollectionService::delete_collection(
+            collection.id,
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let events = event_bus.events.lock();
+        assert_eq!(events[1].event_type, "collection:deleted");
+        assert_eq!(
+            events[1].data,
+            serde_json::to_value(CollectionDeleted {
+                collection_id: collection.id
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn remove_video_moves_it_to_trash() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection = CollectionService::create_collection(
+            vec![VideoToAdd {
+                path: "foo/video.mp4".parse().unwrap(),
+                thumbnail: "".to_string(),
+                size_bytes: 0,
+                duration_seconds: 0,
+            }],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        CollectionService::remove_video(
+            collection.id,
+            collection.videos[0].id,
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let retrieved_collection = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert!(retrieved_collection.videos.is_empty());
+        assert_eq!(
+            repositories().trash().list()[0].content,
+            Trashed::Video {
+                collection_id: collection.id,
+                video: collection.videos[0].clone(),
+            }
+        );
+        let events = event_bus.events.lock();
+        assert_eq!(events[2].event_type, "video:removed");
+        assert_eq!(
+            events[2].data,
+            serde_json::to_value(VideoRemovedFromCollection {
+                collection_id: collection.id,
+                video_id: collection.videos[0].id,
+            })
+            .unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod collection_service_reorganise_tests {
+    use crate::collections::collection_builder::{a_collection, a_video};
+    use crate::collections::collections::collection_service_setup::setup;
+    use crate::collections::collections::Style::{Blues, Rock};
+    use crate::collections::collections::{CollectionService, Video};
+    use crate::collections::events::{CollectionUpdated, VideoAddedToCollection};
+    use crate::collections::trash::Trashed;
+    use crate::event_bus::EventBusManager;
+    use crate::repositories::repositories;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn move_videos_between_collections() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let moved = a_video().build();
+        let kept = a_video().build();
+        let from = a_collection().add_video(&moved).add_video(&kept).build();
+        let to = a_collection().build();
+        repositories().collections().add(from.clone()).unwrap();
+        repositories().collections().add(to.clone()).unwrap();
+
+        CollectionService::move_videos(
+            from.id,
+            to.id,
+            vec![moved.id],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let from = repositories().collections().get_by_id(&from.id).unwrap();
+        let to = repositories().collections().get_by_id(&to.id).unwrap();
+        assert_eq!(from.videos, vec![kept]);
+        assert_eq!(to.videos, vec![moved]);
+        let now = Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap());
+        assert_eq!((from.updated_at, to.updated_at), (now, now));
+        let events = event_bus.events.lock();
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(
+            event_types,
+            vec![
+                "video:removed",
+                "video:added",
+                "collection:updated",
+                "collection:updated"
+            ]
+        );
+        assert_eq!(
+            events[2].data,
+            serde_json::to_value(CollectionUpdated::from(&from)).unwrap()
+        );
+        assert_eq!(
+            events[3].data,
+            serde_json::to_value(CollectionUpdated::from(&to)).unwrap()
+        );
+    }
+
+    #[test]
+    fn copy_videos_between_collections() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let video = a_video().build();
+        let from = a_collection().add_video(&video).build();
+        let to = a_collection().build();
+        repositories().collections().add(from.clone()).unwrap();
+        repositories().collections().add(to.clone()).unwrap();
+
+        CollectionService::copy_videos(
+            from.id,
+            to.id,
+            vec![video.id],
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+
+        let from = repositories().collections().get_by_id(&from.id).unwrap();
+        let to = repositories().collections().get_by_id(&to.id).unwrap();
+        assert_eq!(from.videos, vec![video.clone()]);
+        assert_eq!(to.videos.len(), 1);
+        assert_ne!(to.videos[0].id, video.id);
+        assert_eq!(
+            to.videos[0],
+            Video {
+                id: to.videos[0].id,
+                ..video
             }
+        );
+        let events = event_bus.events.lock();
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(event_types, vec!["video:added", "collection:updated"]);
+    }
+
+    #[test]
+    fn merge_collections_keeps_edited_metadata() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let mut shared_in_source = a_video().build();
+        shared_in_source.artist = "Interpol".to_string();
+        shared_in_source.style = vec![Rock];
+        shared_in_source.tags = vec!["intro".to_string()];
+        let mut shared_in_target = shared_in_source.clone();
+        shared_in_target.artist = "".to_string();
+        shared_in_target.style = vec![Blues];
+        shared_in_target.tags = vec!["solo".to_string()];
+        let only_in_source = a_video().build();
+        let source = a_collection()
+            .add_video(&shared_in_source)
+            .add_video(&only_in_source)
+            .build();
+        let target = a_collection().add_video(&shared_in_target).build();
+        repositories().collections().add(source.clone()).unwrap();
+        repositories().collections().add(target.clone()).unwrap();
+
+        let merged = CollectionService::merge_collections(
+            source.id,
+            target.id,
+            EventBusManager::new(event_bus.clone()),
         )
+        .unwrap();
+
+        assert_eq!(repositories().collections().list(), vec![merged.clone()]);
+        let trash = repositories().trash().list();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].content, Trashed::Collection { collection: source });
+        assert_eq!(merged.videos.len(), 2);
+        assert_eq!(merged.videos[0].artist, "Interpol");
+        assert_eq!(merged.videos[0].style, vec![Blues, Rock]);
+        assert_eq!(
+            merged.videos[0].tags,
+            vec!["solo".to_string(), "intro".to_string()]
+        );
+        assert_eq!(merged.videos[1], only_in_source);
+        let events = event_bus.events.lock();
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(
+            event_types,
+            vec![
+                "video:updated",
+                "video:added",
+                "collection:updated",
+                "collection:deleted"
+            ]
+        );
+        assert_eq!(
+            events[0].data,
+            serde_json::to_value(VideoAddedToCollection::from((&merged.videos[0], merged.id)))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn cannot_move_videos_into_same_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection = a_collection().add_video(&a_video().build()).build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+
+        let result = CollectionService::move_videos(
+            collection.id,
+            collection.id,
+            vec![collection.videos[0].id],
+            EventBusManager::new(event_bus.clone()),
+        );
+
+        assert!(result.is_err());
     }
 }
@@ -1,11 +1,13 @@
 use crate::collections::collections::{Collection, Style, Video};
 use crate::infra::files::file_manager::VideoData;
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use uuid::Uuid;
 
 #[derive(serde::Serialize, Clone)]
 pub struct VideoAddedToCollection {
     pub collection_id: Uuid,
+    pub id: Uuid,
     pub path: PathBuf,
     pub name: String,
     pub artist: String,
@@ -15,12 +17,14 @@ pub struct VideoAddedToCollection {
     pub thumbnail: String,
     pub size_bytes: u64,
     pub duration_seconds: u64,
+    pub added_at: Option<DateTime<Utc>>,
 }
 
 impl From<(&Video, Uuid)> for VideoAddedToCollection {
     fn from((video, collection_id): (&Video, Uuid)) -> Self {
         Self {
             collection_id,
+            id: video.id,
             path: video.path.clone(),
             name: video.name.clone(),
             artist: video.artist.clone(),
@@ -30,6 +34,7 @@ impl From<(&Video, Uuid)> for VideoAddedToCollection {
             thumbnail: video.thumbnail.clone(),
             size_bytes: video.size_bytes,
             duration_seconds: video.duration_seconds,
+            added_at: video.added_at,
         }
     }
 }
@@ -69,3 +74,44 @@ impl From<&Collection> for CollectionCreated {
         }
     }
 }
+
+#[derive(serde::Serialize, Clone)]
+pub struct CollectionUpdated {
+    pub collection_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub cover: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Collection> for CollectionUpdated {
+    fn from(collection: &Collection) -> Self {
+        Self {
+            collection_id: collection.id,
+            title: collection.title.clone(),
+            description: collection.description.clone(),
+            cover: collection.cover.clone(),
+            updated_at: collection.updated_at,
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct CollectionDeleted {
+    pub collection_id: Uuid,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct VideoRemovedFromCollection {
+    pub collection_id: Uuid,
+    pub video_id: Uuid,
+}
+
+impl From<(&Video, Uuid)> for VideoRemovedFromCollection {
+    fn from((video, collection_id): (&Video, Uuid)) -> Self {
+        Self {
+            collection_id,
+            video_id: video.id,
+        }
+    }
+}
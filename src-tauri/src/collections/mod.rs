@@ -4,4 +4,5 @@ pub mod collections;
 pub mod events;
 pub mod tauri_commands;
 pub mod tauri_dtos;
+pub mod trash;
 pub mod video;
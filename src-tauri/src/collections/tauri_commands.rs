@@ -1,28 +1,28 @@
-use crate::collections::collections::{Collection, CollectionService, VideoToAdd};
-use crate::collections::tauri_dtos::VideoDataDTO;
+use crate::collections::collections::{
+    Collection, CollectionService, CollectionToUpdate, VideoToAdd,
+};
+use crate::collections::tauri_dtos::{CollectionDTO, VideoDataDTO};
+use crate::collections::trash::{TrashItem, TrashService};
 use crate::collections::video::{ThumbnailItem, VideoCollectionToUpdate, VideoFileManager};
+use crate::error::AppError;
 use crate::event_bus::EventBusManager;
 use crate::infra::event_bus::tauri_event_bus::TauriEventBus;
 use crate::infra::files::file_manager::FileManagerForHardDrive;
 use crate::infra::tauri::path::allow_path;
 use crate::repositories::repositories;
-use crate::search::search_service::{ApplyPathRights, Indexer, SearchService, TantivyIndexer};
-use once_cell::sync::Lazy;
+use crate::search::tauri_commands::{indexing_bus_manager, smart_collections};
+use std::path::Path;
 use std::sync::Arc;
-use tantivy::schema::Field;
-use tantivy::IndexWriter;
 use tauri::AppHandle;
-
-static SEARCH_SERVICE: Lazy<SearchService<IndexWriter, Field>> =
-    Lazy::new(|| SearchService::new(TantivyIndexer::initialize()));
+use uuid::Uuid;
 
 #[tauri::command]
 pub async fn retrieve_videos_data(
     app: AppHandle,
     paths: Vec<String>,
-) -> Result<Vec<VideoDataDTO>, String> {
+) -> Result<Vec<VideoDataDTO>, AppError> {
     for path in &paths {
-        allow_path(&app, path)?;
+        allow_path(&app, Path::new(path))?;
     }
     let video_file_manager = VideoFileManager::new(Box::new(FileManagerForHardDrive::new()));
     let video_data = video_file_manager.file_manager.retrieve_all_videos_data(
@@ -37,14 +37,12 @@ pub async fn retrieve_videos_data(
 pub async fn create_collection(
     app: AppHandle,
     videos: Vec<VideoToAdd>,
-) -> Result<Vec<ThumbnailItem>, String> {
+) -> Result<Vec<ThumbnailItem>, AppError> {
     for video in &videos {
-        allow_path(&app, video.clone().path.to_str().unwrap_or("unknown"))?;
+        allow_path(&app, &video.path)?;
     }
-    let collection = CollectionService::create_collection(
-        videos,
-        EventBusManager::new(Arc::new(TauriEventBus::new(app.clone()))),
-    );
+    let collection =
+        CollectionService::create_collection(videos, indexing_bus_manager(app.clone()))?;
     Ok(collection
         .videos
         .iter()
@@ -58,37 +56,121 @@ pub async fn create_collection(
 }
 
 #[tauri::command]
-pub async fn update_video(app: AppHandle, video: VideoCollectionToUpdate) -> Result<(), String> {
-    allow_path(&app, video.video.path.to_str().unwrap())?;
-    CollectionService::update_video(
-        video,
-        EventBusManager::new(Arc::new(TauriEventBus::new(app))),
-    );
-    Ok(())
+pub async fn add_videos_to_collection(
+    app: AppHandle,
+    collection_id: Uuid,
+    videos: Vec<VideoToAdd>,
+) -> Result<Collection, AppError> {
+    for video in &videos {
+        allow_path(&app, &video.path)?;
+    }
+    CollectionService::add_videos_to_collection(
+        collection_id,
+        videos,
+        indexing_bus_manager(app.clone()),
+    )
+}
+
+#[tauri::command]
+pub async fn update_video(app: AppHandle, video: VideoCollectionToUpdate) -> Result<(), AppError> {
+    allow_path(&app, &video.video.path)?;
+    CollectionService::update_video(video, indexing_bus_manager(app))
 }
 
-// TODO: Refactor get_collections to return a CollectionDTO instead of a domain Collection
+/// Regular collections followed by the smart ones.
 #[tauri::command]
-pub async fn get_collections(app: AppHandle) -> Result<Vec<Collection>, String> {
-    let collections = repositories().collections().list();
+pub async fn get_collections(app: AppHandle) -> Result<Vec<CollectionDTO>, AppError> {
+    let mut collections: Vec<CollectionDTO> = repositories()
+        .collections()
+        .list()
+        .into_iter()
+        .map(CollectionDTO::from)
+        .collect();
+    collections.extend(smart_collections()?.into_iter().map(CollectionDTO::from));
     for c in &collections {
-        for v in &c.videos {
-            allow_path(&app, v.path.to_str().unwrap())?;
+        for v in &c.collection.videos {
+            allow_path(&app, &v.path)?;
         }
     }
     Ok(collections)
 }
 
 #[tauri::command]
-pub async fn search_videos(app: AppHandle, query: String) -> Result<(), String> {
-    SEARCH_SERVICE.initialize(EventBusManager::new(Arc::new(TauriEventBus::new(
-        app.clone(),
-    ))));
-    if !query.is_empty() {
-        SEARCH_SERVICE.index_all_videos()?;
-    }
-    let app_clone = app.clone();
-    let callback: ApplyPathRights = Box::new(move |path| allow_path(&app_clone, path));
-    SEARCH_SERVICE.search(&query, Some(&callback))?;
-    Ok(())
+pub async fn update_collection(
+    app: AppHandle,
+    collection: CollectionToUpdate,
+) -> Result<Collection, AppError> {
+    CollectionService::update_collection(collection, indexing_bus_manager(app))
+}
+
+#[tauri::command]
+pub async fn delete_collection(app: AppHandle, collection_id: Uuid) -> Result<(), AppError> {
+    CollectionService::delete_collection(collection_id, indexing_bus_manager(app))
+}
+
+#[tauri::command]
+pub async fn remove_video(
+    app: AppHandle,
+    collection_id: Uuid,
+    video_id: Uuid,
+) -> Result<(), AppError> {
+    CollectionService::remove_video(collection_id, video_id, indexing_bus_manager(app))
+}
+
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashItem>, AppError> {
+    Ok(TrashService::list())
+}
+
+#[tauri::command]
+pub async fn restore_from_trash(app: AppHandle, trash_item_id: Uuid) -> Result<(), AppError> {
+    TrashService::restore(trash_item_id, indexing_bus_manager(app))
+}
+
+#[tauri::command]
+pub async fn purge_trash(trash_item_id: Option<Uuid>) -> Result<(), AppError> {
+    TrashService::purge(trash_item_id)
+}
+
+#[tauri::command]
+pub async fn move_videos(
+    app: AppHandle,
+    from_collection_id: Uuid,
+    to_collection_id: Uuid,
+    video_ids: Vec<Uuid>,
+) -> Result<(), AppError> {
+    CollectionService::move_videos(
+        from_collection_id,
+        to_collection_id,
+        video_ids,
+        indexing_bus_manager(app),
+    )
+}
+
+#[tauri::command]
+pub async fn copy_videos(
+    app: AppHandle,
+    from_collection_id: Uuid,
+    to_collection_id: Uuid,
+    video_ids: Vec<Uuid>,
+) -> Result<(), AppError> {
+    CollectionService::copy_videos(
+        from_collection_id,
+        to_collection_id,
+        video_ids,
+        indexing_bus_manager(app),
+    )
+}
+
+#[tauri::command]
+pub async fn merge_collections(
+    app: AppHandle,
+    source_collection_id: Uuid,
+    target_collection_id: Uuid,
+) -> Result<Collection, AppError> {
+    CollectionService::merge_collections(
+        source_collection_id,
+        target_collection_id,
+        indexing_bus_manager(app),
+    )
 }
@@ -1,4 +1,7 @@
+use crate::collections::collections::{Collection, Video};
 use crate::infra::files::file_manager::VideoData;
+use crate::search::search_request::SearchRequest;
+use crate::search::smart_collections::SmartCollection;
 use std::path::PathBuf;
 
 #[derive(serde::Serialize, Clone)]
@@ -19,3 +22,32 @@ impl From<&VideoData> for VideoDataDTO {
         }
     }
 }
+
+#[derive(serde::Serialize, Clone)]
+pub struct CollectionDTO {
+    #[serde(flatten)]
+    pub collection: Collection,
+    pub smart: bool,
+    /// The request defining a smart collection.
+    pub request: Option<SearchRequest>,
+}
+
+impl From<Collection> for CollectionDTO {
+    fn from(collection: Collection) -> Self {
+        Self {
+            collection,
+            smart: false,
+            request: None,
+        }
+    }
+}
+
+impl From<(SmartCollection, Vec<Video>)> for CollectionDTO {
+    fn from((smart_collection, videos): (SmartCollection, Vec<Video>)) -> Self {
+        Self {
+            collection: smart_collection.to_collection(videos),
+            smart: true,
+            request: Some(smart_collection.request),
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::clock::clock;
+use crate::collections::collections::{Collection, CollectionService, Video};
+use crate::collections::events::{CollectionCreated, VideoAddedToCollection};
+use crate::error::AppError;
+use crate::event_bus::EventBusManager;
+use crate::repositories::repositories;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum Trashed {
+    Collection { collection: Collection },
+    Video { collection_id: Uuid, video: Video },
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TrashItem {
+    pub id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+    pub content: Trashed,
+}
+
+impl TrashItem {
+    pub fn new(content: Trashed) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            deleted_at: clock().now(),
+            content,
+        }
+    }
+}
+
+pub trait TrashRepository: Send + Sync {
+    fn list(&self) -> Vec<TrashItem>;
+    fn add(&self, item: TrashItem) -> Result<(), AppError>;
+    fn get_by_id(&self, id: &Uuid) -> Option<TrashItem>;
+    fn delete(&self, id: &Uuid) -> Result<(), AppError>;
+}
+
+#[derive(Default)]
+pub struct TrashRepositoryMemory {
+    items: parking_lot::Mutex<Vec<TrashItem>>,
+}
+
+impl TrashRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TrashRepository for TrashRepositoryMemory {
+    fn list(&self) -> Vec<TrashItem> {
+        self.items.lock().clone()
+    }
+
+    fn add(&self, item: TrashItem) -> Result<(), AppError> {
+        self.items.lock().push(item);
+        Ok(())
+    }
+
+    fn get_by_id(&self, id: &Uuid) -> Option<TrashItem> {
+        self.items.lock().iter().find(|i| i.id == *id).cloned()
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        self.items.lock().retain(|i| i.id != *id);
+        Ok(())
+    }
+}
+
+const RESTORED_COLLECTION_TITLE: &str = "Restored videos";
+
+pub struct TrashService {}
+
+impl TrashService {
+    pub fn list() -> Vec<TrashItem> {
+        repositories().trash().list()
+    }
+
+    pub fn restore(trash_item_id: Uuid, bus_manager: EventBusManager) -> Result<(), AppError> {
+        let item = repositories()
+            .trash()
+            .get_by_id(&trash_item_id)
+            .ok_or(AppError::not_found("Trash item", trash_item_id))?;
+
+        match item.content {
+            Trashed::Collection { collection } => {
+                repositories().collections().add(collection.clone())?;
+                bus_manager.publish("collection:created", CollectionCreated::from(&collection));
+                collection.videos.iter().for_each(|video| {
+                    bus_manager.publish(
+                        "video:added",
+                        VideoAddedToCollection::from((video, collection.id)),
+                    )
+                });
+            }
+            Trashed::Video {
+                collection_id,
+                video,
+            } => {
+                Self::ensure_collection(collection_id, &bus_manager)?;
+                let mut collection = CollectionService::find_collection(&collection_id)?;
+                collection.add_video(video.clone());
+                repositories().collections().add(collection)?;
+                bus_manager.publish(
+                    "video:added",
+                    VideoAddedToCollection::from((&video, collection_id)),
+                );
+            }
+        }
+        repositories().trash().delete(&trash_item_id)
+    }
+
+    /// Brings back the collection a trashed video belonged to when it is gone:
+    /// from the trash if it was deleted, or as a new empty collection with the
+    /// same id if it was purged, so the video always has somewhere to go.
+    fn ensure_collection(
+        collection_id: Uuid,
+        bus_manager: &EventBusManager,
+    ) -> Result<(), AppError> {
+        if repositories()
+            .collections()
+            .get_by_id(&collection_id)
+            .is_some()
+        {
+            return Ok(());
+        }
+        let trashed_collection = repositories().trash().list().into_iter().find(|item| {
+            matches!(&item.content, Trashed::Collection { collection } if collection.id == collection_id)
+        });
+        if let Some(item) = trashed_collection {
+            return Self::restore(item.id, bus_manager.clone());
+        }
+        let collection = Collection::new(collection_id, RESTORED_COLLECTION_TITLE);
+        repositories().collections().add(collection.clone())?;
+        bus_manager.publish("collection:created", CollectionCreated::from(&collection));
+        Ok(())
+    }
+
+    pub fn purge(trash_item_id: Option<Uuid>) -> Result<(), AppError> {
+        match trash_item_id {
+            Some(id) => repositories().trash().delete(&id),
+            None => repositories()
+                .trash()
+                .list()
+                .iter()
+                .try_for_each(|item| repositories().trash().delete(&item.id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod trash_service_tests {
+    use crate::collections::collection_builder::{a_collection, a_video};
+    use crate::collections::collections::collection_service_setup::setup;
+    use crate::collections::collections::CollectionService;
+    use crate::collections::trash::TrashService;
+    use crate::event_bus::EventBusManager;
+    use crate::repositories::repositories;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn restore_deleted_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let collection = a_collection()
+            .with_title("Lessons with John")
+            .add_video(&a_video().build())
+            .build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        CollectionService::delete_collection(
+            collection.id,
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+        let trash_item = repositories().trash().list()[0].clone();
+
+        TrashService::restore(trash_item.id, EventBusManager::new(event_bus.clone())).unwrap();
+
+        assert_eq!(
+            repositories().collections().get_by_id(&collection.id),
+            Some(collection)
+        );
+        assert!(repositories().trash().list().is_empty());
+        let events = event_bus.events.lock();
+        assert_eq!(events[1].event_type, "collection:created");
+        assert_eq!(events[2].event_type, "video:added");
+    }
+
+    #[test]
+    fn restore_removed_video() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let video = a_video().build();
+        let collection = a_collection().add_video(&video).build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        CollectionService::remove_video(
+            collection.id,
+            video.id,
+            EventBusManager::new(event_bus.clone()),
+        )
+        .unwrap();
+        let trash_item = repositories().trash().list()[0].clone();
+
+        TrashService::restore(trash_item.id, EventBusManager::new(event_bus.clone())).unwrap();
+
+        assert_eq!(
+            repositories()
+                .collections()
+                .get_by_id(&collection.id)
+                .unwrap()
+                .videos,
+            vec![video]
+        );
+        assert!(repositories().trash().list().is_empty());
+    }
+
+    #[test]
+    fn restore_video_from_deleted_collection_restores_the_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let video = a_video().build();
+        let other_video = a_video().build();
+        let collection = a_collection()
+            .with_title("Lessons with John")
+            .add_video(&video)
+            .add_video(&other_video)
+            .build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        let bus_manager = EventBusManager::new(event_bus.clone());
+        CollectionService::remove_video(collection.id, video.id, bus_manager.clone()).unwrap();
+        CollectionService::delete_collection(collection.id, bus_manager.clone()).unwrap();
+        let video_item = repositories().trash().list()[0].clone();
+
+        TrashService::restore(video_item.id, bus_manager).unwrap();
+
+        let restored = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert_eq!(restored.title, "Lessons with John");
+        assert_eq!(restored.videos, vec![other_video, video]);
+        assert!(repositories().trash().list().is_empty());
+    }
+
+    #[test]
+    fn restore_video_from_purged_collection_recreates_the_collection() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        let video = a_video().build();
+        let collection = a_collection().add_video(&video).build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        let bus_manager = EventBusManager::new(event_bus.clone());
+        CollectionService::remove_video(collection.id, video.id, bus_manager.clone()).unwrap();
+        let video_item = repositories().trash().list()[0].clone();
+        CollectionService::delete_collection(collection.id, bus_manager.clone()).unwrap();
+        let collection_item = repositories().trash().list()[1].clone();
+        TrashService::purge(Some(collection_item.id)).unwrap();
+
+        TrashService::restore(video_item.id, bus_manager).unwrap();
+
+        let restored = repositories()
+            .collections()
+            .get_by_id(&collection.id)
+            .unwrap();
+        assert_eq!(restored.title, "Restored videos");
+        assert_eq!(restored.videos, vec![video]);
+        assert!(repositories().trash().list().is_empty());
+        let events = event_bus.events.lock();
+        let restore_events: Vec<_> = events[events.len() - 2..]
+            .iter()
+            .map(|e| e.event_type.as_str())
+            .collect();
+        assert_eq!(restore_events, vec!["collection:created", "video:added"]);
+    }
+
+    #[test]
+    fn purge_whole_trash() {
+        let (event_bus, _clock_guard, _repositories_guard) =
+            setup(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0));
+        for _ in 0..2 {
+            let collection = a_collection().build();
+            repositories()
+                .collections()
+                .add(collection.clone())
+                .unwrap();
+            CollectionService::delete_collection(
+                collection.id,
+                EventBusManager::new(event_bus.clone()),
+            )
+            .unwrap();
+        }
+
+        TrashService::purge(None).unwrap();
+
+        assert!(repositories().trash().list().is_empty());
+        assert!(repositories().collections().list().is_empty());
+    }
+}
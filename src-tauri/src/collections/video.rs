@@ -1,5 +1,6 @@
 use crate::collections::collections::Style;
 use crate::collections::events::VideoDataRetrieved;
+use crate::error::AppError;
 use crate::event_bus::EventBusManager;
 use crate::infra::files::file_manager::VideoData;
 use std::path::PathBuf;
@@ -20,6 +21,7 @@ pub struct VideoCollectionToUpdate {
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct VideoToUpdate {
+    pub id: uuid::Uuid,
     pub path: PathBuf,
     pub name: String,
     pub artist: String,
@@ -36,9 +38,9 @@ pub trait FileManager {
         &self,
         paths: Vec<String>,
         event_bus_manager: EventBusManager,
-    ) -> Result<Vec<VideoData>, String> {
+    ) -> Result<Vec<VideoData>, AppError> {
         if paths.is_empty() {
-            return Err("Aucun fichier reçu".to_string());
+            return Err(AppError::invalid_input("No file received"));
         }
 
         let mut result = vec![];
@@ -53,7 +55,7 @@ pub trait FileManager {
         Ok(result)
     }
 
-    fn retrieve_video_data(&self, path: &str) -> Result<VideoData, String>;
+    fn retrieve_video_data(&self, path: &str) -> Result<VideoData, AppError>;
 }
 
 pub struct VideoFileManager {
@@ -70,6 +72,7 @@ impl VideoFileManager {
 mod video_file_manager_tests {
     use crate::collections::events::VideoDataRetrieved;
     use crate::collections::video::{FileManager, VideoFileManager};
+    use crate::error::AppError;
     use crate::event_bus::EventBusManager;
     use crate::infra::event_bus::memory_event_bus::MemoryEventBus;
     use crate::infra::files::file_manager::VideoData;
@@ -79,7 +82,7 @@ mod video_file_manager_tests {
     struct FileManagerMemory {}
 
     impl FileManager for FileManagerMemory {
-        fn retrieve_video_data(&self, path: &str) -> Result<VideoData, String> {
+        fn retrieve_video_data(&self, path: &str) -> Result<VideoData, AppError> {
             Ok(VideoData {
                 path: path.parse().unwrap(),
                 thumbnail: "".parse().unwrap(),
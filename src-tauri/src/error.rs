@@ -0,0 +1,214 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppError {
+    NotFound {
+        entity: String,
+        id: String,
+    },
+    InvalidInput {
+        reason: String,
+    },
+    InvalidPath {
+        path: String,
+        reason: String,
+    },
+    MediaToolMissing {
+        tool: String,
+    },
+    MediaToolFailed {
+        tool: String,
+        reason: String,
+    },
+    Io {
+        path: Option<String>,
+        reason: String,
+    },
+    InvalidData {
+        path: String,
+        reason: String,
+    },
+    IndexError {
+        reason: String,
+    },
+    InvalidQuery {
+        token: String,
+        position: usize,
+        reason: String,
+    },
+    Storage {
+        reason: String,
+    },
+    Cancelled {
+        operation: String,
+        id: String,
+    },
+}
+
+impl AppError {
+    pub fn not_found(entity: &str, id: impl Display) -> Self {
+        AppError::NotFound {
+            entity: entity.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    pub fn invalid_input(reason: impl Display) -> Self {
+        AppError::InvalidInput {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_path(path: &Path, reason: impl Display) -> Self {
+        AppError::InvalidPath {
+            path: path.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn io(path: &Path, reason: impl Display) -> Self {
+        AppError::Io {
+            path: Some(path.to_string_lossy().to_string()),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_data(path: &Path, reason: impl Display) -> Self {
+        AppError::InvalidData {
+            path: path.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn index(reason: impl Display) -> Self {
+        AppError::IndexError {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_query(token: &str, position: usize, reason: impl Display) -> Self {
+        AppError::InvalidQuery {
+            token: token.to_string(),
+            position,
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn cancelled(operation: &str, id: impl Display) -> Self {
+        AppError::Cancelled {
+            operation: operation.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound { .. } => "NotFound",
+            AppError::InvalidInput { .. } => "InvalidInput",
+            AppError::InvalidPath { .. } => "InvalidPath",
+            AppError::MediaToolMissing { .. } => "MediaToolMissing",
+            AppError::MediaToolFailed { .. } => "MediaToolFailed",
+            AppError::Io { .. } => "Io",
+            AppError::InvalidData { .. } => "InvalidData",
+            AppError::IndexError { .. } => "IndexError",
+            AppError::InvalidQuery { .. } => "InvalidQuery",
+            AppError::Storage { .. } => "Storage",
+            AppError::Cancelled { .. } => "Cancelled",
+        }
+    }
+
+    fn details(&self) -> Value {
+        match self {
+            AppError::NotFound { entity, id } => json!({ "entity": entity, "id": id }),
+            AppError::InvalidInput { reason } => json!({ "reason": reason }),
+            AppError::InvalidPath { path, reason } => json!({ "path": path, "reason": reason }),
+            AppError::MediaToolMissing { tool } => json!({ "tool": tool }),
+            AppError::MediaToolFailed { tool, reason } => json!({ "tool": tool, "reason": reason }),
+            AppError::Io { path, reason } => json!({ "path": path, "reason": reason }),
+            AppError::InvalidData { path, reason } => json!({ "path": path, "reason": reason }),
+            AppError::IndexError { reason } => json!({ "reason": reason }),
+            AppError::InvalidQuery {
+                token,
+                position,
+                reason,
+            } => json!({ "token": token, "position": position, "reason": reason }),
+            AppError::Storage { reason } => json!({ "reason": reason }),
+            AppError::Cancelled { operation, id } => json!({ "operation": operation, "id": id }),
+        }
+    }
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotFound { entity, id } => write!(f, "{entity} {id} not found"),
+            AppError::InvalidInput { reason } => write!(f, "Invalid input: {reason}"),
+            AppError::InvalidPath { path, reason } => write!(f, "Invalid path {path}: {reason}"),
+            AppError::MediaToolMissing { tool } => write!(f, "{tool} could not be launched"),
+            AppError::MediaToolFailed { tool, reason } => write!(f, "{tool} failed: {reason}"),
+            AppError::Io {
+                path: Some(path),
+                reason,
+            } => write!(f, "I/O error on {path}: {reason}"),
+            AppError::Io { path: None, reason } => write!(f, "I/O error: {reason}"),
+            AppError::InvalidData { path, reason } => write!(f, "Invalid data in {path}: {reason}"),
+            AppError::IndexError { reason } => write!(f, "Search index error: {reason}"),
+            AppError::InvalidQuery {
+                token,
+                position,
+                reason,
+            } => write!(f, "Invalid query: {reason} at \"{token}\" ({position})"),
+            AppError::Storage { reason } => write!(f, "Storage error: {reason}"),
+            AppError::Cancelled { operation, id } => write!(f, "{operation} {id} was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+impl From<tantivy::TantivyError> for AppError {
+    fn from(e: tantivy::TantivyError) -> Self {
+        AppError::index(e)
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        AppError::Storage {
+            reason: e.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod app_error_tests {
+    use crate::error::AppError;
+
+    #[test]
+    fn serializes_with_stable_code_and_details() {
+        let error = AppError::not_found("Collection", "42");
+
+        assert_eq!(
+            serde_json::to_value(error).unwrap(),
+            serde_json::json!({
+                "code": "NotFound",
+                "message": "Collection 42 not found",
+                "details": { "entity": "Collection", "id": "42" }
+            })
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::event_bus::{Event, EventBus};
+use crate::search::search_service::{SearchService, WriterCommit};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// Collects the events of one command, then applies them to the search index
+/// in a single commit before forwarding them to `inner`, so listeners that
+/// search again on an event already see the change. The events are flushed
+/// when the bus is dropped, at the end of the command.
+pub struct IndexingEventBus<T: WriterCommit + 'static, U: 'static> {
+    inner: Arc<dyn EventBus>,
+    search_service: &'static SearchService<T, U>,
+    pending: Mutex<Vec<Event>>,
+}
+
+impl<T: WriterCommit + 'static, U: 'static> IndexingEventBus<T, U> {
+    pub fn new(inner: Arc<dyn EventBus>, search_service: &'static SearchService<T, U>) -> Self {
+        Self {
+            inner,
+            search_service,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<T: WriterCommit + 'static, U: 'static> EventBus for IndexingEventBus<T, U> {
+    fn publish(&self, event: Event) {
+        self.pending.lock().push(event);
+    }
+}
+
+impl<T: WriterCommit + 'static, U: 'static> Drop for IndexingEventBus<T, U> {
+    fn drop(&mut self) {
+        let events = std::mem::take(self.pending.get_mut());
+        if events.is_empty() {
+            return;
+        }
+        if let Err(e) = self.search_service.handle_events(&events) {
+            log::error!("Failed to update the search index: {e}");
+        }
+        for event in events {
+            self.inner.publish(event);
+        }
+    }
+}
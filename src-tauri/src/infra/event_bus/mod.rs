@@ -1,3 +1,4 @@
+pub mod indexing_event_bus;
 #[cfg(test)]
 pub mod memory_event_bus;
 pub mod tauri_event_bus;
@@ -0,0 +1,132 @@
+use crate::error::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".bak");
+    path.with_file_name(file_name)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+/// Writes `value` next to `path`, syncs it to disk and renames it over `path`,
+/// keeping the previous version as a `.bak` file.
+pub fn write_json_atomically<T: Serialize>(path: &Path, value: &T) -> Result<(), AppError> {
+    replace_json(path, value, true)
+}
+
+fn replace_json<T: Serialize>(path: &Path, value: &T, keep_backup: bool) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+    }
+
+    let temp = temp_path(path);
+    let result = write_synced(&temp, value);
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+
+    if keep_backup && path.exists() {
+        let backup = backup_path(path);
+        fs::copy(path, &backup).map_err(|e| AppError::io(&backup, e))?;
+    }
+    fs::rename(&temp, path).map_err(|e| AppError::io(path, e))?;
+
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+fn write_synced<T: Serialize>(path: &Path, value: &T) -> Result<(), AppError> {
+    let file = File::create(path).map_err(|e| AppError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, value)
+        .map_err(|e| AppError::invalid_data(path, e))?;
+    writer.flush().map_err(|e| AppError::io(path, e))?;
+    writer
+        .get_ref()
+        .sync_all()
+        .map_err(|e| AppError::io(path, e))
+}
+
+/// Reads `path`, falling back to its `.bak` copy when the file is damaged.
+/// A recovered backup is written back over the damaged file. A file that
+/// cannot be read at all is reported as is, without touching it.
+pub fn read_json_or_recover<T: Serialize + DeserializeOwned>(path: &Path) -> Result<T, AppError> {
+    let error = match read_json(path) {
+        Ok(value) => return Ok(value),
+        Err(e @ AppError::InvalidData { .. }) => e,
+        Err(e) => return Err(e),
+    };
+
+    let backup = backup_path(path);
+    if !backup.exists() {
+        return Err(error);
+    }
+    let value: T = read_json(&backup)?;
+    log::warn!("Recovered {path:?} from its backup after: {error}");
+    replace_json(path, &value, false)?;
+    Ok(value)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    serde_json::from_str(&content).map_err(|e| AppError::invalid_data(path, e))
+}
+
+#[cfg(test)]
+mod atomic_file_tests {
+    use crate::infra::files::atomic_file::{
+        backup_path, read_json_or_recover, write_json_atomically,
+    };
+    use serde_json::{json, Value};
+    use std::fs;
+
+    #[test]
+    fn keeps_previous_version_and_recovers_truncated_file() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        let path = base_dir.join("collection.json");
+        write_json_atomically(&path, &json!({"title": "first"})).unwrap();
+        write_json_atomically(&path, &json!({"title": "second"})).unwrap();
+        fs::write(&path, "{\"title\": \"sec").unwrap();
+
+        let recovered: Value = read_json_or_recover(&path).unwrap();
+
+        assert_eq!(recovered, json!({"title": "first"}));
+        let on_disk: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(on_disk, json!({"title": "first"}));
+        let backup: Value =
+            serde_json::from_str(&fs::read_to_string(backup_path(&path)).unwrap()).unwrap();
+        assert_eq!(backup, json!({"title": "first"}));
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn tells_damaged_files_from_missing_ones() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let damaged = base_dir.join("damaged.json");
+        fs::write(&damaged, "{\"title\": \"sec").unwrap();
+
+        let damaged_error = read_json_or_recover::<Value>(&damaged).unwrap_err();
+        let missing_error =
+            read_json_or_recover::<Value>(&base_dir.join("missing.json")).unwrap_err();
+
+        assert_eq!(damaged_error.code(), "InvalidData");
+        assert_eq!(missing_error.code(), "Io");
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
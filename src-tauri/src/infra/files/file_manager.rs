@@ -1,4 +1,5 @@
 use crate::collections::video::FileManager;
+use crate::error::AppError;
 use base64::Engine;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -47,7 +48,26 @@ pub struct FfmpegMetadata {
     pub duration_seconds: u64,
 }
 
-fn get_video_duration_ffprobe(video_path: &Path) -> Result<u64, String> {
+fn launch_error(tool: &str, e: std::io::Error) -> AppError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::MediaToolMissing {
+            tool: tool.to_string(),
+        },
+        _ => AppError::Io {
+            path: None,
+            reason: format!("Failed to launch {tool}: {e}"),
+        },
+    }
+}
+
+fn tool_failed(tool: &str, reason: impl std::fmt::Display) -> AppError {
+    AppError::MediaToolFailed {
+        tool: tool.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+pub(crate) fn get_video_duration_ffprobe(video_path: &Path) -> Result<u64, AppError> {
     let output = get_ffprobe_command()
         .args([
             "-v",
@@ -59,22 +79,22 @@ fn get_video_duration_ffprobe(video_path: &Path) -> Result<u64, String> {
             video_path.to_string_lossy().as_ref(),
         ])
         .output()
-        .map_err(|e| format!("Impossible de lancer ffprobe: {e}"))?;
+        .map_err(|e| launch_error("ffprobe", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        return Err(format!("ffprobe a échoué: {stderr}"));
+        return Err(tool_failed("ffprobe", stderr));
     }
 
     let duration_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
     let duration = duration_str
         .parse::<f64>()
-        .map_err(|e| format!("Impossible de parser la durée {}: {}", duration_str, e))?;
+        .map_err(|e| tool_failed("ffprobe", format!("Invalid duration {duration_str}: {e}")))?;
 
     Ok(duration as u64)
 }
 
-fn generate_one_thumbnail_ffmpeg(video_path: &Path) -> Result<FfmpegMetadata, String> {
+fn generate_one_thumbnail_ffmpeg(video_path: &Path) -> Result<FfmpegMetadata, AppError> {
     let output = get_ffmpeg_command()
         .args([
             "-hide_banner",
@@ -97,11 +117,11 @@ fn generate_one_thumbnail_ffmpeg(video_path: &Path) -> Result<FfmpegMetadata, St
             "pipe:1",
         ])
         .output()
-        .map_err(|e| format!("Impossible de lancer ffmpeg: {e}"))?;
+        .map_err(|e| launch_error("ffmpeg", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        return Err(format!("ffmpeg a échoué: {stderr}"));
+        return Err(tool_failed("ffmpeg", stderr));
     }
 
     let b64 = base64::engine::general_purpose::STANDARD.encode(output.stdout);
@@ -122,9 +142,10 @@ impl FileManagerForHardDrive {
         Self {}
     }
 
-    fn create_thumbnail(video_path: &PathBuf) -> Result<Option<FfmpegMetadata>, String> {
+    fn create_thumbnail(video_path: &PathBuf) -> Result<Option<FfmpegMetadata>, AppError> {
         match generate_one_thumbnail_ffmpeg(&video_path) {
             Ok(metadata) => Ok(Some(metadata)),
+            Err(e @ AppError::MediaToolMissing { .. }) => Err(e),
             Err(e) => {
                 log::error!("Failed to generate thumbnail {video_path:?}: {e}");
                 Ok(None)
@@ -142,7 +163,7 @@ pub struct VideoData {
 }
 
 impl FileManager for FileManagerForHardDrive {
-    fn retrieve_video_data(&self, path: &str) -> Result<VideoData, String> {
+    fn retrieve_video_data(&self, path: &str) -> Result<VideoData, AppError> {
         let video_path = PathBuf::from(path);
         let size_bytes = std::fs::metadata(&video_path).map(|m| m.len()).unwrap_or(0);
         let metadata = Self::create_thumbnail(&video_path)?;
@@ -1 +1,2 @@
+pub mod atomic_file;
 pub mod file_manager;
@@ -1,10 +1,11 @@
 use crate::collections::collections::{Collection, CollectionRepository};
+use crate::collections::trash::{TrashItem, TrashRepository};
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{backup_path, read_json_or_recover, write_json_atomically};
+use crate::infra::repositories::sqlite_repositories::CollectionRepositorySqlite;
 use crate::repositories::{set_repositories, Repositories};
-use chrono::Local;
 use std::fs;
-use std::fs::File;
-use std::io::BufWriter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -17,43 +18,8 @@ impl CollectionRepositoryFile {
         Self { base_dir }
     }
 
-    fn find_collection_file_path(&self, id: &Uuid) -> Option<PathBuf> {
-        let collections_dir = self.base_dir.clone();
-        if !collections_dir.exists() {
-            return None;
-        }
-
-        let entries = match fs::read_dir(&collections_dir) {
-            Ok(entries) => entries,
-            Err(e) => {
-                log::error!("Failed to read directory {collections_dir:?}: {e}");
-                return None;
-            }
-        };
-
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(e) => {
-                    log::error!("Failed to read directory entry: {e}");
-                    continue;
-                }
-            };
-            let path = entry.path();
-            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
-                continue;
-            }
-
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(collection) = serde_json::from_str::<Collection>(&content) {
-                    if collection.id == *id {
-                        return Some(path);
-                    }
-                }
-            }
-        }
-
-        None
+    pub fn collection_path(base_dir: &Path, id: &Uuid) -> PathBuf {
+        base_dir.join(format!("collection-{id}.json"))
     }
 }
 
@@ -95,60 +61,128 @@ impl CollectionRepository for CollectionRepositoryFile {
                 continue;
             }
 
-            match fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str::<Collection>(&content) {
-                    Ok(collection) => collections.push(collection),
-                    Err(e) => log::error!("Failed to parse JSON from {path:?}: {e}"),
-                },
-                Err(e) => log::error!("Failed to read file {path:?}: {e}"),
+            match read_json_or_recover::<Collection>(&path) {
+                Ok(collection) => collections.push(collection),
+                Err(e) => log::error!("Failed to load collection: {e}"),
             }
         }
 
         collections
     }
 
-    fn add(&self, _c: Collection) {
-        let existing_path = if let Some(_existing) = self.get_by_id(&_c.id) {
-            self.find_collection_file_path(&_c.id)
-        } else {
-            None
-        };
+    fn add(&self, _c: Collection) -> Result<(), AppError> {
+        write_json_atomically(&Self::collection_path(&self.base_dir, &_c.id), &_c)
+    }
 
-        let path = if let Some(existing_path) = existing_path {
-            existing_path
-        } else {
-            let now = Local::now();
-            let file_name = format!("collection-{}.json", now.format("%Y-%m-%d_%H-%M-%S"));
-            self.base_dir.join(file_name)
+    fn get_by_id(&self, id: &Uuid) -> Option<Collection> {
+        let path = Self::collection_path(&self.base_dir, id);
+        if !path.exists() {
+            return None;
+        }
+        match read_json_or_recover(&path) {
+            Ok(collection) => Some(collection),
+            Err(e) => {
+                log::error!("Failed to load collection: {e}");
+                None
+            }
+        }
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let path = Self::collection_path(&self.base_dir, id);
+        if !path.exists() {
+            return Err(AppError::not_found("Collection", id));
+        }
+        fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+        let backup = backup_path(&path);
+        if backup.exists() {
+            fs::remove_file(&backup).map_err(|e| AppError::io(&backup, e))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct TrashRepositoryFile {
+    base_dir: PathBuf,
+}
+
+impl TrashRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self { base_dir }
+    }
+
+    fn item_path(&self, id: &Uuid) -> PathBuf {
+        self.base_dir.join(format!("{id}.json"))
+    }
+}
+
+impl TrashRepository for TrashRepositoryFile {
+    fn list(&self) -> Vec<TrashItem> {
+        let entries = match fs::read_dir(&self.base_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
         };
 
-        if let Some(parent) = path.parent() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                log::error!("Impossible de créer le dossier {parent:?}: {e}");
-                return;
+        let mut items = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
             }
-        }
 
-        let file = match File::create(&path) {
-            Ok(f) => f,
-            Err(e) => {
-                log::error!("Impossible de créer {path:?}: {e}");
-                return;
+            match fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str::<TrashItem>(&content) {
+                    Ok(item) => items.push(item),
+                    Err(e) => log::error!("Failed to parse JSON from {path:?}: {e}"),
+                },
+                Err(e) => log::error!("Failed to read file {path:?}: {e}"),
             }
-        };
-        let writer = BufWriter::new(file);
+        }
+        items.sort_by_key(|item| item.deleted_at);
+        items
+    }
 
-        if let Err(e) = serde_json::to_writer_pretty(writer, &_c) {
-            log::error!("Impossible d'écrire le JSON: {e}");
+    fn add(&self, item: TrashItem) -> Result<(), AppError> {
+        write_json_atomically(&self.item_path(&item.id), &item)
+    }
+
+    fn get_by_id(&self, id: &Uuid) -> Option<TrashItem> {
+        let content = fs::read_to_string(self.item_path(id)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let path = self.item_path(id);
+        if !path.exists() {
+            return Err(AppError::not_found("Trash item", id));
         }
+        fs::remove_file(&path).map_err(|e| AppError::io(&path, e))
     }
+}
 
-    fn get_by_id(&self, id: &Uuid) -> Option<Collection> {
-        self.list().into_iter().find(|c| c.id == *id)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageBackend {
+    Json,
+    Sqlite,
+}
+
+impl StorageBackend {
+    pub fn from_env() -> Self {
+        match std::env::var("ALEXANDRIA_STORAGE").as_deref() {
+            Ok("sqlite") => StorageBackend::Sqlite,
+            _ => StorageBackend::Json,
+        }
     }
 }
 
-pub fn init_prod(base_dir: PathBuf) {
-    let file_repo = CollectionRepositoryFile::new(base_dir);
-    set_repositories(Repositories::new(Arc::new(file_repo)));
+pub fn init_prod(base_dir: PathBuf, backend: StorageBackend) -> Result<(), AppError> {
+    let trash_repo = TrashRepositoryFile::new(base_dir.join("trash"));
+    let collections_repo: Arc<dyn CollectionRepository> = match backend {
+        StorageBackend::Json => Arc::new(CollectionRepositoryFile::new(base_dir)),
+        StorageBackend::Sqlite => Arc::new(CollectionRepositorySqlite::new(
+            CollectionRepositorySqlite::database_path(&base_dir),
+        )?),
+    };
+    set_repositories(Repositories::new(collections_repo, Arc::new(trash_repo)));
+    Ok(())
 }
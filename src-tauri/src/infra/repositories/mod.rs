@@ -1 +1,2 @@
 pub mod file_repositories;
+pub mod sqlite_repositories;
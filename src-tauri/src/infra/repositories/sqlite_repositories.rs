@@ -0,0 +1,416 @@
+use crate::collections::collections::{Collection, CollectionRepository, Style, Video};
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS collections (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        description TEXT,
+        cover TEXT,
+        created_at TEXT,
+        updated_at TEXT
+    );
+    CREATE TABLE IF NOT EXISTS videos (
+        collection_id TEXT NOT NULL REFERENCES collections(id),
+        id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        path TEXT NOT NULL,
+        name TEXT NOT NULL,
+        artist TEXT NOT NULL,
+        song TEXT NOT NULL,
+        thumbnail TEXT NOT NULL,
+        size_bytes INTEGER NOT NULL,
+        duration_seconds INTEGER NOT NULL,
+        added_at TEXT,
+        PRIMARY KEY (collection_id, id)
+    );
+    CREATE TABLE IF NOT EXISTS video_tags (
+        collection_id TEXT NOT NULL,
+        video_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        tag TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS video_styles (
+        collection_id TEXT NOT NULL,
+        video_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        style TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS video_tags_by_collection ON video_tags (collection_id);
+    CREATE INDEX IF NOT EXISTS video_styles_by_collection ON video_styles (collection_id);
+    CREATE TABLE IF NOT EXISTS json_imports (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        imported_at TEXT NOT NULL
+    );
+";
+
+pub struct CollectionRepositorySqlite {
+    connection: Mutex<Connection>,
+}
+
+impl CollectionRepositorySqlite {
+    pub fn new(database_path: PathBuf) -> Result<Self, AppError> {
+        Self::with_connection(Connection::open(database_path)?)
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, AppError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, AppError> {
+        connection.execute_batch(SCHEMA)?;
+        Self::add_missing_columns(&connection)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Columns added after the first release, missing from older databases.
+    fn add_missing_columns(connection: &Connection) -> Result<(), AppError> {
+        let has_added_at = connection
+            .prepare("SELECT 1 FROM pragma_table_info('videos') WHERE name = 'added_at'")?
+            .exists([])?;
+        if !has_added_at {
+            connection.execute("ALTER TABLE videos ADD COLUMN added_at TEXT", [])?;
+        }
+        Ok(())
+    }
+
+    pub fn database_path(base_dir: &Path) -> PathBuf {
+        base_dir.join("collections.db")
+    }
+
+    /// When the collections of the JSON store were last imported, if ever.
+    pub fn last_json_import(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        let imported_at: Option<String> = self
+            .connection
+            .lock()
+            .query_row(
+                "SELECT imported_at FROM json_imports WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(imported_at.and_then(|d| d.parse().ok()))
+    }
+
+    pub fn record_json_import(&self, imported_at: DateTime<Utc>) -> Result<(), AppError> {
+        self.connection.lock().execute(
+            "INSERT INTO json_imports (id, imported_at) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET imported_at = excluded.imported_at",
+            params![imported_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    fn load_collections(
+        connection: &Connection,
+        id: Option<&Uuid>,
+    ) -> Result<Vec<Collection>, AppError> {
+        let filter = id.map(|id| id.to_string());
+        let mut tags = Self::load_labels(connection, "video_tags", "tag", filter.as_deref())?;
+        let mut styles = Self::load_labels(connection, "video_styles", "style", filter.as_deref())?;
+
+        let mut statement = connection.prepare(
+            "SELECT collection_id, id, path, name, artist, song, thumbnail, size_bytes, duration_seconds, added_at
+             FROM videos WHERE ?1 IS NULL OR collection_id = ?1 ORDER BY collection_id, position",
+        )?;
+        let mut videos: HashMap<String, Vec<Video>> = HashMap::new();
+        let rows = statement.query_map(params![filter], |row| {
+            let collection_id: String = row.get(0)?;
+            let id: String = row.get(1)?;
+            Ok((
+                collection_id,
+                id,
+                row.get::<_, String>(2)?,
+                Video {
+                    id: Uuid::nil(),
+                    path: PathBuf::new(),
+                    name: row.get(3)?,
+                    artist: row.get(4)?,
+                    song: row.get(5)?,
+                    style: vec![],
+                    tags: vec![],
+                    thumbnail: row.get(6)?,
+                    size_bytes: row.get::<_, i64>(7)? as u64,
+                    duration_seconds: row.get::<_, i64>(8)? as u64,
+                    added_at: row
+                        .get::<_, Option<String>>(9)?
+                        .and_then(|d| d.parse().ok()),
+                },
+            ))
+        })?;
+        for row in rows {
+            let (collection_id, id, path, mut video) = row?;
+            let key = (collection_id.clone(), id.clone());
+            video.id = parse_uuid(&id)?;
+            video.path = PathBuf::from(path);
+            video.tags = tags.remove(&key).unwrap_or_default();
+            video.style = styles
+                .remove(&key)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|s| serde_json::from_value::<Style>(s.into()).ok())
+                .collect();
+            videos.entry(collection_id).or_default().push(video);
+        }
+
+        let mut statement = connection.prepare(
+            "SELECT id, title, description, cover, created_at, updated_at
+             FROM collections WHERE ?1 IS NULL OR id = ?1 ORDER BY created_at, rowid",
+        )?;
+        let rows = statement.query_map(params![filter], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+        let mut collections = Vec::new();
+        for row in rows {
+            let (id, title, description, cover, created_at, updated_at) = row?;
+            collections.push(Collection {
+                id: parse_uuid(&id)?,
+                title,
+                description,
+                cover,
+                created_at: created_at.and_then(|d| d.parse().ok()),
+                updated_at: updated_at.and_then(|d| d.parse().ok()),
+                videos: videos.remove(&id).unwrap_or_default(),
+            });
+        }
+        Ok(collections)
+    }
+
+    fn load_labels(
+        connection: &Connection,
+        table: &str,
+        column: &str,
+        collection_id: Option<&str>,
+    ) -> Result<HashMap<(String, String), Vec<String>>, AppError> {
+        let mut statement = connection.prepare(&format!(
+            "SELECT collection_id, video_id, {column} FROM {table}
+             WHERE ?1 IS NULL OR collection_id = ?1 ORDER BY position"
+        ))?;
+        let rows = statement.query_map(params![collection_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut labels: HashMap<(String, String), Vec<String>> = HashMap::new();
+        for row in rows {
+            let (collection_id, video_id, label) = row?;
+            labels
+                .entry((collection_id, video_id))
+                .or_default()
+                .push(label);
+        }
+        Ok(labels)
+    }
+
+    fn delete_videos(transaction: &Transaction, collection_id: &str) -> Result<(), AppError> {
+        for table in ["video_tags", "video_styles", "videos"] {
+            transaction.execute(
+                &format!("DELETE FROM {table} WHERE collection_id = ?1"),
+                params![collection_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn insert_video(
+        transaction: &Transaction,
+        collection_id: &str,
+        position: usize,
+        video: &Video,
+    ) -> Result<(), AppError> {
+        let video_id = video.id.to_string();
+        transaction.execute(
+            "INSERT INTO videos (collection_id, id, position, path, name, artist, song, thumbnail, size_bytes, duration_seconds, added_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                collection_id,
+                video_id,
+                position as i64,
+                video.path.to_string_lossy(),
+                video.name,
+                video.artist,
+                video.song,
+                video.thumbnail,
+                video.size_bytes as i64,
+                video.duration_seconds as i64,
+                video.added_at.map(|d| d.to_rfc3339()),
+            ],
+        )?;
+        for (position, tag) in video.tags.iter().enumerate() {
+            transaction.execute(
+                "INSERT INTO video_tags (collection_id, video_id, position, tag) VALUES (?1, ?2, ?3, ?4)",
+                params![collection_id, video_id, position as i64, tag],
+            )?;
+        }
+        for (position, style) in video.style.iter().enumerate() {
+            let style = serde_json::to_value(style)
+                .ok()
+                .and_then(|s| s.as_str().map(str::to_string))
+                .unwrap_or_default();
+            transaction.execute(
+                "INSERT INTO video_styles (collection_id, video_id, position, style) VALUES (?1, ?2, ?3, ?4)",
+                params![collection_id, video_id, position as i64, style],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_uuid(value: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(value).map_err(|e| AppError::Storage {
+        reason: format!("Invalid identifier {value}: {e}"),
+    })
+}
+
+impl CollectionRepository for CollectionRepositorySqlite {
+    fn list(&self) -> Vec<Collection> {
+        Self::load_collections(&self.connection.lock(), None).unwrap_or_else(|e| {
+            log::error!("Failed to list collections: {e}");
+            Vec::new()
+        })
+    }
+
+    fn add(&self, c: Collection) -> Result<(), AppError> {
+        let mut connection = self.connection.lock();
+        let transaction = connection.transaction()?;
+        let collection_id = c.id.to_string();
+        transaction.execute(
+            "INSERT INTO collections (id, title, description, cover, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                description = excluded.description,
+                cover = excluded.cover,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at",
+            params![
+                collection_id,
+                c.title,
+                c.description,
+                c.cover,
+                c.created_at.map(|d| d.to_rfc3339()),
+                c.updated_at.map(|d| d.to_rfc3339()),
+            ],
+        )?;
+        Self::delete_videos(&transaction, &collection_id)?;
+        for (position, video) in c.videos.iter().enumerate() {
+            Self::insert_video(&transaction, &collection_id, position, video)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn get_by_id(&self, id: &Uuid) -> Option<Collection> {
+        match Self::load_collections(&self.connection.lock(), Some(id)) {
+            Ok(collections) => collections.into_iter().next(),
+            Err(e) => {
+                log::error!("Failed to get collection {id}: {e}");
+                None
+            }
+        }
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let mut connection = self.connection.lock();
+        let transaction = connection.transaction()?;
+        let collection_id = id.to_string();
+        let existing: Option<String> = transaction
+            .query_row(
+                "SELECT id FROM collections WHERE id = ?1",
+                params![collection_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if existing.is_none() {
+            return Err(AppError::not_found("Collection", id));
+        }
+        Self::delete_videos(&transaction, &collection_id)?;
+        transaction.execute(
+            "DELETE FROM collections WHERE id = ?1",
+            params![collection_id],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod collection_repository_sqlite_tests {
+    use crate::collections::collection_builder::{a_collection, a_video};
+    use crate::collections::collections::CollectionRepository;
+    use crate::collections::collections::Style::{CountryFolk, HardRock};
+    use crate::error::AppError;
+    use crate::infra::repositories::sqlite_repositories::CollectionRepositorySqlite;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn stores_and_retrieves_collections_with_videos() {
+        let repository = CollectionRepositorySqlite::in_memory().unwrap();
+        let mut video = a_video()
+            .with_added_at(Utc.with_ymd_and_hms(2026, 1, 20, 9, 30, 0).unwrap())
+            .build();
+        video.style = vec![HardRock, CountryFolk];
+        video.tags = vec!["solo".to_string(), "intro".to_string()];
+        let mut collection = a_collection()
+            .with_title("Lessons with John")
+            .add_video(&video)
+            .add_video(&a_video().build())
+            .build();
+        collection.description = Some("Weekly lessons".to_string());
+        collection.created_at = Some(Utc.with_ymd_and_hms(2026, 1, 28, 12, 0, 0).unwrap());
+
+        repository.add(collection.clone()).unwrap();
+
+        assert_eq!(
+            repository.get_by_id(&collection.id),
+            Some(collection.clone())
+        );
+        assert_eq!(repository.list(), vec![collection]);
+    }
+
+    #[test]
+    fn replaces_an_existing_collection() {
+        let repository = CollectionRepositorySqlite::in_memory().unwrap();
+        let mut collection = a_collection().add_video(&a_video().build()).build();
+        repository.add(collection.clone()).unwrap();
+
+        collection.title = "Renamed".to_string();
+        collection.videos = vec![a_video().build()];
+        repository.add(collection.clone()).unwrap();
+
+        assert_eq!(repository.list(), vec![collection]);
+    }
+
+    #[test]
+    fn deletes_a_collection() {
+        let repository = CollectionRepositorySqlite::in_memory().unwrap();
+        let collection = a_collection().add_video(&a_video().build()).build();
+        repository.add(collection.clone()).unwrap();
+
+        repository.delete(&collection.id).unwrap();
+
+        assert!(repository.list().is_empty());
+        assert_eq!(
+            repository.delete(&collection.id),
+            Err(AppError::not_found("Collection", collection.id))
+        );
+    }
+}
@@ -1,7 +1,9 @@
+use crate::error::AppError;
+use std::path::Path;
 use tauri::{AppHandle, Manager, Runtime};
 
-pub fn allow_path<R: Runtime>(app: &AppHandle<R>, path: &str) -> Result<(), String> {
+pub fn allow_path<R: Runtime>(app: &AppHandle<R>, path: &Path) -> Result<(), AppError> {
     app.asset_protocol_scope()
         .allow_file(path)
-        .map_err(|e: tauri::Error| e.to_string())
+        .map_err(|e: tauri::Error| AppError::invalid_path(path, e))
 }
@@ -1,9 +1,15 @@
 use crate::collections::tauri_commands as collection_commands;
-use crate::infra::repositories::file_repositories::init_prod;
+use crate::infra::repositories::file_repositories::{init_prod, StorageBackend};
+use crate::search::saved_searches::{SavedSearchRepositoryFile, SearchHistoryRepositoryFile};
+use crate::search::search_settings::SearchSettingsRepositoryFile;
+use crate::search::smart_collections::SmartCollectionRepositoryFile;
+use crate::search::synonyms::SynonymRepositoryFile;
+use crate::search::tauri_commands as search_commands;
 use tauri::Manager;
 
 mod clock;
 mod collections;
+mod error;
 mod event_bus;
 mod infra;
 mod migrations;
@@ -36,25 +42,74 @@ pub fn run() {
                     .expect("Failed to create app data directory");
             }
 
-            init_prod(app_data_dir.clone());
+            let storage_backend = StorageBackend::from_env();
+            init_prod(app_data_dir.clone(), storage_backend)?;
 
             let migration_repo = migrations::MigrationRepositoryFile::new(app_data_dir.clone());
             let migration_manager =
-                migrations::MigrationManager::new(Box::new(migration_repo), app_data_dir);
-            if let Err(e) =
-                migration_manager.play(vec![Box::new(migrations::VideoDurationMigration)])
-            {
+                migrations::MigrationManager::new(Box::new(migration_repo), app_data_dir.clone());
+            let migrations: Vec<Box<dyn migrations::Migration>> = vec![
+                Box::new(migrations::VideoDurationMigration),
+                Box::new(migrations::VideoIdMigration),
+                Box::new(migrations::CollectionFileNameMigration),
+                Box::new(migrations::VideoAddedAtMigration),
+            ];
+            if let Err(e) = migration_manager.play(migrations) {
                 log::error!("Failed to play migrations: {}", e);
             }
+            if storage_backend == StorageBackend::Sqlite {
+                if let Err(e) = migrations::SqliteImport.run(&app_data_dir) {
+                    log::error!("Failed to import collections into sqlite: {}", e);
+                }
+            }
+
+            if let Err(e) = search_commands::init_search_service(
+                &app_data_dir.join("search-index"),
+                Box::new(SearchSettingsRepositoryFile::new(app_data_dir.clone())),
+                Box::new(SynonymRepositoryFile::new(app_data_dir.clone())),
+                Box::new(SavedSearchRepositoryFile::new(app_data_dir.clone())),
+                Box::new(SearchHistoryRepositoryFile::new(app_data_dir.clone())),
+                Box::new(SmartCollectionRepositoryFile::new(app_data_dir.clone())),
+                app.handle().clone(),
+            ) {
+                log::error!("Failed to initialize the search index: {}", e);
+            }
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             collection_commands::retrieve_videos_data,
             collection_commands::create_collection,
+            collection_commands::add_videos_to_collection,
             collection_commands::update_video,
+            collection_commands::update_collection,
             collection_commands::get_collections,
-            collection_commands::search_videos
+            collection_commands::delete_collection,
+            collection_commands::remove_video,
+            collection_commands::move_videos,
+            collection_commands::copy_videos,
+            collection_commands::merge_collections,
+            collection_commands::list_trash,
+            collection_commands::restore_from_trash,
+            collection_commands::purge_trash,
+            search_commands::search_videos,
+            search_commands::suggest_search_terms,
+            search_commands::similar_videos,
+            search_commands::reindex_videos,
+            search_commands::get_search_settings,
+            search_commands::update_search_settings,
+            search_commands::get_synonyms,
+            search_commands::add_synonyms,
+            search_commands::remove_synonyms,
+            search_commands::get_search_history,
+            search_commands::get_saved_searches,
+            search_commands::save_search,
+            search_commands::rename_saved_search,
+            search_commands::delete_saved_search,
+            search_commands::run_saved_search,
+            search_commands::create_smart_collection,
+            search_commands::update_smart_collection,
+            search_commands::delete_smart_collection
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,11 +1,17 @@
+use crate::collections::collections::{Collection, CollectionRepository};
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{backup_path, read_json_or_recover, write_json_atomically};
+use crate::infra::files::file_manager::get_video_duration_ffprobe;
+use crate::infra::repositories::file_repositories::CollectionRepositoryFile;
+use crate::infra::repositories::sqlite_repositories::CollectionRepositorySqlite;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 pub trait Migration: Send + Sync {
     fn id(&self) -> &str;
-    fn run(&self, base_dir: &Path) -> Result<(), String>;
+    fn run(&self, base_dir: &Path) -> Result<(), AppError>;
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -74,7 +80,7 @@ impl MigrationManager {
         }
     }
 
-    pub fn play(&self, migrations: Vec<Box<dyn Migration>>) -> Result<(), String> {
+    pub fn play(&self, migrations: Vec<Box<dyn Migration>>) -> Result<(), AppError> {
         for migration in migrations {
             if !self.repository.exists(migration.id()) {
                 migration.run(&self.base_dir)?;
@@ -95,16 +101,15 @@ impl Migration for VideoDurationMigration {
         "video_duration_migration_001"
     }
 
-    fn run(&self, base_dir: &Path) -> Result<(), String> {
+    fn run(&self, base_dir: &Path) -> Result<(), AppError> {
         if !base_dir.exists() {
             return Ok(());
         }
 
-        let entries = fs::read_dir(&base_dir)
-            .map_err(|e| format!("Failed to read directory {:?}: {}", base_dir, e))?;
+        let entries = fs::read_dir(base_dir).map_err(|e| AppError::io(base_dir, e))?;
 
         for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let entry = entry.map_err(|e| AppError::io(base_dir, e))?;
 
             let path = entry.path();
             if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
@@ -145,11 +150,7 @@ impl Migration for VideoDurationMigration {
                     }
 
                     if updated {
-                        if let Ok(updated_content) = serde_json::to_string_pretty(&json) {
-                            fs::write(&path, updated_content).map_err(|e| {
-                                format!("Failed to write updated collection to {:?}: {}", path, e)
-                            })?;
-                        }
+                        write_json_atomically(&path, &json)?;
                     }
                 }
             }
@@ -158,68 +159,272 @@ impl Migration for VideoDurationMigration {
     }
 }
 
-fn get_video_duration_ffprobe(video_path: &Path) -> Result<u64, String> {
-    let output = get_ffprobe_command()
-        .args([
-            "-v",
-            "error",
-            "-show_entries",
-            "format=duration",
-            "-of",
-            "default=noprint_wrappers=1:nokey=1",
-            video_path.to_string_lossy().as_ref(),
-        ])
-        .output()
-        .map_err(|e| format!("Impossible de lancer ffprobe: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        return Err(format!("ffprobe a échoué: {stderr}"));
+pub struct VideoIdMigration;
+
+impl VideoIdMigration {
+    fn backfill_ids(videos: &mut [serde_json::Value]) -> bool {
+        let mut updated = false;
+        for video in videos {
+            if video.get("id").is_none() {
+                video["id"] = serde_json::Value::from(uuid::Uuid::new_v4().to_string());
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    fn migrate_file(path: &Path) -> Result<(), AppError> {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(());
+        };
+        let Ok(mut json) = serde_json::from_str::<serde_json::Value>(&content) else {
+            return Ok(());
+        };
+
+        let mut updated = false;
+        if let Some(videos) = json.get_mut("videos").and_then(|v| v.as_array_mut()) {
+            updated |= Self::backfill_ids(videos);
+        }
+        if let Some(trashed) = json.get_mut("content") {
+            if let Some(videos) = trashed
+                .get_mut("collection")
+                .and_then(|c| c.get_mut("videos"))
+                .and_then(|v| v.as_array_mut())
+            {
+                updated |= Self::backfill_ids(videos);
+            }
+            if let Some(video) = trashed.get_mut("video") {
+                updated |= Self::backfill_ids(std::slice::from_mut(video));
+            }
+        }
+
+        if updated {
+            write_json_atomically(path, &json)?;
+        }
+        Ok(())
+    }
+}
+
+impl Migration for VideoIdMigration {
+    fn id(&self) -> &str {
+        "video_id_migration_002"
+    }
+
+    fn run(&self, base_dir: &Path) -> Result<(), AppError> {
+        for dir in [base_dir.to_path_buf(), base_dir.join("trash")] {
+            if !dir.exists() {
+                continue;
+            }
+
+            let entries = fs::read_dir(&dir).map_err(|e| AppError::io(&dir, e))?;
+
+            for entry in entries {
+                let entry = entry.map_err(|e| AppError::io(&dir, e))?;
+
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                    continue;
+                }
+                Self::migrate_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct CollectionFileNameMigration;
+
+impl CollectionFileNameMigration {
+    /// Settles a legacy file whose collection already has a file named after
+    /// its id, as an interrupted run leaves: an identical legacy file is
+    /// dropped, otherwise the last updated version is kept and the other one
+    /// moved to its `.bak` file.
+    fn resolve_conflict(
+        legacy: &Path,
+        legacy_json: &serde_json::Value,
+        target: &Path,
+    ) -> Result<(), AppError> {
+        let target_json = read_json_or_recover::<serde_json::Value>(target)?;
+        if &target_json == legacy_json {
+            return fs::remove_file(legacy).map_err(|e| AppError::io(legacy, e));
+        }
+        let updated_at = |json: &serde_json::Value| {
+            json.get("updated_at")
+                .and_then(|v| serde_json::from_value::<DateTime<Utc>>(v.clone()).ok())
+        };
+        if updated_at(legacy_json) > updated_at(&target_json) {
+            let backup = backup_path(target);
+            fs::rename(target, &backup).map_err(|e| AppError::io(&backup, e))?;
+            fs::rename(legacy, target).map_err(|e| AppError::io(legacy, e))
+        } else {
+            let backup = backup_path(legacy);
+            fs::rename(legacy, &backup).map_err(|e| AppError::io(&backup, e))
+        }
+    }
+}
+
+impl Migration for CollectionFileNameMigration {
+    fn id(&self) -> &str {
+        "collection_file_name_migration_004"
+    }
+
+    fn run(&self, base_dir: &Path) -> Result<(), AppError> {
+        if !base_dir.exists() {
+            return Ok(());
+        }
+
+        let entries = fs::read_dir(base_dir).map_err(|e| AppError::io(base_dir, e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| AppError::io(base_dir, e))?;
+
+            let path = entry.path();
+            let is_collection = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("collection-"));
+            if !is_collection || path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let json = match read_json_or_recover::<serde_json::Value>(&path) {
+                Ok(json) => json,
+                Err(e) => {
+                    log::error!("Skipping unreadable collection file: {e}");
+                    continue;
+                }
+            };
+            let Some(id) = json
+                .get("id")
+                .and_then(|id| id.as_str())
+                .and_then(|id| uuid::Uuid::parse_str(id).ok())
+            else {
+                continue;
+            };
+
+            let target = CollectionRepositoryFile::collection_path(base_dir, &id);
+            if target == path {
+                continue;
+            }
+            if target.exists() {
+                Self::resolve_conflict(&path, &json, &target)?;
+                continue;
+            }
+            fs::rename(&path, &target).map_err(|e| AppError::io(&path, e))?;
+            let backup = backup_path(&path);
+            if backup.exists() {
+                fs::rename(&backup, backup_path(&target)).map_err(|e| AppError::io(&backup, e))?;
+            }
+        }
+        Ok(())
     }
+}
 
-    let duration_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let duration = duration_str
-        .parse::<f64>()
-        .map_err(|e| format!("Impossible de parser la durée {}: {}", duration_str, e))?;
+/// Videos added before `added_at` existed are dated with their collection creation.
+pub struct VideoAddedAtMigration;
 
-    Ok(duration as u64)
+impl VideoAddedAtMigration {
+    fn backfill(repository: &dyn CollectionRepository) -> Result<(), AppError> {
+        for mut collection in repository.list() {
+            let Some(created_at) = collection.created_at else {
+                continue;
+            };
+            let mut updated = false;
+            for video in collection
+                .videos
+                .iter_mut()
+                .filter(|v| v.added_at.is_none())
+            {
+                video.added_at = Some(created_at);
+                updated = true;
+            }
+            if updated {
+                repository.add(collection)?;
+            }
+        }
+        Ok(())
+    }
 }
 
-fn get_ffprobe_command() -> Command {
-    let command = "ffprobe";
-    #[cfg(target_os = "windows")]
-    let paths = [command, &format!("{}.exe", command)];
-
-    #[cfg(target_os = "macos")]
-    let paths = [
-        command,
-        &format!("/opt/homebrew/bin/{}", command),
-        &format!("/usr/local/bin/{}", command),
-    ];
-
-    #[cfg(target_os = "linux")]
-    let paths = [
-        command,
-        &format!("/usr/bin/{}", command),
-        &format!("/usr/local/bin/{}", command),
-        &format!("/snap/bin/{}", command),
-    ];
-
-    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-    let paths = [command];
-
-    for path in paths {
-        if Command::new(path).arg("-version").output().is_ok() {
-            return Command::new(path);
+impl Migration for VideoAddedAtMigration {
+    fn id(&self) -> &str {
+        "video_added_at_migration_005"
+    }
+
+    fn run(&self, base_dir: &Path) -> Result<(), AppError> {
+        Self::backfill(&CollectionRepositoryFile::new(base_dir.to_path_buf()))?;
+
+        let database_path = CollectionRepositorySqlite::database_path(base_dir);
+        if database_path.exists() {
+            let repository = CollectionRepositorySqlite::new(database_path)?;
+            Self::backfill(&repository)?;
         }
+        Ok(())
     }
+}
 
-    Command::new(command)
+/// Imports the JSON collections into the sqlite store. It runs on every start
+/// with the sqlite backend rather than once, so collections added or edited
+/// while using the file backend show up after switching back: files changed
+/// since the last import replace their sqlite copy, and collections missing
+/// from sqlite are added. Untouched files are left alone, so collections
+/// deleted in sqlite stay deleted.
+pub struct SqliteImport;
+
+impl SqliteImport {
+    pub fn run(&self, base_dir: &Path) -> Result<(), AppError> {
+        let target =
+            CollectionRepositorySqlite::new(CollectionRepositorySqlite::database_path(base_dir))?;
+        let imported_at = Utc::now();
+        let last_import = target.last_json_import()?;
+
+        let entries = fs::read_dir(base_dir).map_err(|e| AppError::io(base_dir, e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| AppError::io(base_dir, e))?;
+
+            let path = entry.path();
+            let is_collection = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("collection-"));
+            if !is_collection || path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let modified: DateTime<Utc> = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| AppError::io(&path, e))?
+                .into();
+            if last_import.is_some_and(|last_import| modified < last_import) {
+                continue;
+            }
+            let collection = match read_json_or_recover::<Collection>(&path) {
+                Ok(collection) => collection,
+                Err(e) => {
+                    log::error!("Skipping unreadable collection file: {e}");
+                    continue;
+                }
+            };
+            let import = match target.get_by_id(&collection.id) {
+                None => true,
+                // Before the first import was recorded, only newer edits win.
+                Some(existing) => {
+                    last_import.is_some() || collection.updated_at > existing.updated_at
+                }
+            };
+            if import {
+                log::info!("Importing collection {} from {path:?}", collection.id);
+                target.add(collection)?;
+            }
+        }
+        target.record_json_import(imported_at)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collections::collection_builder::a_collection;
     use std::sync::Arc;
     use std::sync::Mutex;
 
@@ -232,7 +437,7 @@ mod tests {
         fn id(&self) -> &str {
             &self.id
         }
-        fn run(&self, _base_dir: &Path) -> Result<(), String> {
+        fn run(&self, _base_dir: &Path) -> Result<(), AppError> {
             let mut count = self.run_count.lock().unwrap();
             *count += 1;
             Ok(())
@@ -325,4 +530,188 @@ mod tests {
         assert_eq!(*run_count2.lock().unwrap(), 1, "m2 should have been played");
         assert_eq!(manager.repository.list().len(), 2);
     }
+
+    #[test]
+    fn video_id_migration_backfills_missing_ids() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let existing_id = uuid::Uuid::new_v4().to_string();
+        let collection_path = base_dir.join("collection-2026-01-28_12-00-00.json");
+        fs::write(
+            &collection_path,
+            serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "title": "Collection - 2026-01-28",
+                "videos": [
+                    {"path": "foo/video.mp4"},
+                    {"id": existing_id, "path": "foo/other.mp4"}
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        VideoIdMigration.run(&base_dir).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&collection_path).unwrap()).unwrap();
+        let first_id = json["videos"][0]["id"].as_str().unwrap();
+        assert!(uuid::Uuid::parse_str(first_id).is_ok());
+        assert_eq!(json["videos"][1]["id"].as_str(), Some(existing_id.as_str()));
+        let previous: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(backup_path(&collection_path)).unwrap())
+                .unwrap();
+        assert!(previous["videos"][0].get("id").is_none());
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn video_added_at_migration_uses_collection_creation_date() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let id = uuid::Uuid::new_v4();
+        let dated = "2026-01-20T09:30:00Z";
+        fs::write(
+            CollectionRepositoryFile::collection_path(&base_dir, &id),
+            serde_json::json!({
+                "id": id,
+                "title": "Lessons with John",
+                "created_at": "2026-01-01T10:00:00Z",
+                "videos": [
+                    {"id": uuid::Uuid::new_v4(), "path": "foo/video.mp4", "name": "video.mp4",
+                     "artist": "", "song": "", "style": [], "tags": [], "thumbnail": "",
+                     "size_bytes": 1, "duration_seconds": 1},
+                    {"id": uuid::Uuid::new_v4(), "path": "foo/other.mp4", "name": "other.mp4",
+                     "artist": "", "song": "", "style": [], "tags": [], "thumbnail": "",
+                     "size_bytes": 1, "duration_seconds": 1, "added_at": dated}
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        VideoAddedAtMigration.run(&base_dir).unwrap();
+
+        let collection = CollectionRepositoryFile::new(base_dir.clone())
+            .get_by_id(&id)
+            .unwrap();
+        let added_at: Vec<_> = collection
+            .videos
+            .iter()
+            .map(|v| v.added_at.unwrap().to_rfc3339())
+            .collect();
+        assert_eq!(
+            added_at,
+            vec!["2026-01-01T10:00:00+00:00", "2026-01-20T09:30:00+00:00"]
+        );
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn collection_file_name_migration_renames_files_by_id() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let id = uuid::Uuid::new_v4();
+        let content = serde_json::json!({"id": id, "title": "Lessons with John", "videos": []});
+        fs::write(
+            base_dir.join("collection-2026-01-28_12-00-00.json"),
+            content.to_string(),
+        )
+        .unwrap();
+
+        CollectionFileNameMigration.run(&base_dir).unwrap();
+
+        assert!(!base_dir
+            .join("collection-2026-01-28_12-00-00.json")
+            .exists());
+        let renamed = base_dir.join(format!("collection-{id}.json"));
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&renamed).unwrap()).unwrap();
+        assert_eq!(json, content);
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn collection_file_name_migration_settles_files_already_renamed() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let read = |path: &Path| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+        };
+        let copied_id = uuid::Uuid::new_v4();
+        let copied = serde_json::json!({"id": copied_id, "title": "Lessons", "videos": []});
+        let copied_legacy = base_dir.join("collection-2026-01-01_10-00-00.json");
+        let copied_target = CollectionRepositoryFile::collection_path(&base_dir, &copied_id);
+        fs::write(&copied_legacy, copied.to_string()).unwrap();
+        fs::write(&copied_target, copied.to_string()).unwrap();
+        let edited_id = uuid::Uuid::new_v4();
+        let older = serde_json::json!({
+            "id": edited_id,
+            "title": "Solos",
+            "updated_at": "2026-01-20T09:30:00Z",
+            "videos": []
+        });
+        let newer = serde_json::json!({
+            "id": edited_id,
+            "title": "Guitar solos",
+            "updated_at": "2026-01-28T12:00:00Z",
+            "videos": []
+        });
+        let edited_legacy = base_dir.join("collection-2026-01-02_10-00-00.json");
+        let edited_target = CollectionRepositoryFile::collection_path(&base_dir, &edited_id);
+        fs::write(&edited_legacy, newer.to_string()).unwrap();
+        fs::write(&edited_target, older.to_string()).unwrap();
+
+        CollectionFileNameMigration.run(&base_dir).unwrap();
+
+        assert!(!copied_legacy.exists());
+        assert_eq!(read(&copied_target), copied);
+        assert!(!edited_legacy.exists());
+        assert_eq!(read(&edited_target), newer);
+        assert_eq!(read(&backup_path(&edited_target)), older);
+        assert_eq!(
+            CollectionRepositoryFile::new(base_dir.clone()).list().len(),
+            2
+        );
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+
+    #[test]
+    fn sqlite_import_brings_in_collections_changed_since_the_last_import() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base_dir).unwrap();
+        let files = CollectionRepositoryFile::new(base_dir.clone());
+        let deleted = a_collection().build();
+        let mut edited = a_collection().build();
+        files.add(deleted.clone()).unwrap();
+        files.add(edited.clone()).unwrap();
+        SqliteImport.run(&base_dir).unwrap();
+        let sqlite =
+            CollectionRepositorySqlite::new(CollectionRepositorySqlite::database_path(&base_dir))
+                .unwrap();
+        sqlite.delete(&deleted.id).unwrap();
+        let last_import = Utc::now() - chrono::Duration::hours(1);
+        sqlite.record_json_import(last_import).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(CollectionRepositoryFile::collection_path(
+                &base_dir,
+                &deleted.id,
+            ))
+            .unwrap()
+            .set_modified((last_import - chrono::Duration::hours(1)).into())
+            .unwrap();
+        edited.title = "Edited with the file backend".to_string();
+        files.add(edited.clone()).unwrap();
+        let added = a_collection().build();
+        files.add(added.clone()).unwrap();
+
+        SqliteImport.run(&base_dir).unwrap();
+
+        assert_eq!(sqlite.get_by_id(&deleted.id), None);
+        assert_eq!(sqlite.get_by_id(&edited.id), Some(edited));
+        assert_eq!(sqlite.get_by_id(&added.id), Some(added));
+        assert!(sqlite.last_json_import().unwrap() > Some(last_import));
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
 }
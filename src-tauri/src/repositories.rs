@@ -1,26 +1,39 @@
 use crate::collections::collections::{CollectionRepository, CollectionRepositoryMemory};
+use crate::collections::trash::{TrashRepository, TrashRepositoryMemory};
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use std::sync::Arc;
 
 pub struct Repositories {
     collections: Arc<dyn CollectionRepository>,
+    trash: Arc<dyn TrashRepository>,
 }
 
 impl Repositories {
-    pub fn new(collections_repo: Arc<dyn CollectionRepository>) -> Self {
+    pub fn new(
+        collections_repo: Arc<dyn CollectionRepository>,
+        trash_repo: Arc<dyn TrashRepository>,
+    ) -> Self {
         Self {
             collections: collections_repo,
+            trash: trash_repo,
         }
     }
 
     pub fn collections(&self) -> Arc<dyn CollectionRepository> {
         Arc::clone(&self.collections)
     }
+
+    pub fn trash(&self) -> Arc<dyn TrashRepository> {
+        Arc::clone(&self.trash)
+    }
 }
 
 static REPOSITORIES: Lazy<RwLock<Arc<Repositories>>> = Lazy::new(|| {
-    let default = Repositories::new(Arc::new(CollectionRepositoryMemory::new()));
+    let default = Repositories::new(
+        Arc::new(CollectionRepositoryMemory::new()),
+        Arc::new(TrashRepositoryMemory::new()),
+    );
     RwLock::new(Arc::new(default))
 });
 
@@ -1 +1,9 @@
+pub mod query_syntax;
+pub mod saved_searches;
+pub mod search_request;
+pub mod search_results;
 pub mod search_service;
+pub mod search_settings;
+pub mod smart_collections;
+pub mod synonyms;
+pub mod tauri_commands;
@@ -0,0 +1,197 @@
+//! Search query syntax.
+//!
+//! Words match any text field and `"quoted words"` match as a phrase.
+//! `field:word` and `field:"quoted words"` only look into one field, and a
+//! leading `-` excludes the videos matching a word, phrase or field clause.
+//! Fields are `name`, `artist`, `song`, `style`, `tag` (or `tags`) and
+//! `collection`, matching the collection title. Other `word:` prefixes are
+//! plain text, so titles such as `Re: Stacks` stay searchable.
+//!
+//! Field clauses are required while free words only rank the hits:
+//! `artist:interpol style:"hard rock" tag:solo live` returns the hard rock
+//! solos of Interpol, live ones first.
+
+use crate::error::AppError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryField {
+    Name,
+    Artist,
+    Song,
+    Style,
+    Tags,
+    Collection,
+}
+
+impl QueryField {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "name" => Some(QueryField::Name),
+            "artist" => Some(QueryField::Artist),
+            "song" => Some(QueryField::Song),
+            "style" => Some(QueryField::Style),
+            "tag" | "tags" => Some(QueryField::Tags),
+            "collection" => Some(QueryField::Collection),
+            _ => None,
+        }
+    }
+
+    pub fn schema_name(self) -> &'static str {
+        match self {
+            QueryField::Name => "name",
+            QueryField::Artist => "artist",
+            QueryField::Song => "song",
+            QueryField::Style => "style",
+            QueryField::Tags => "tags",
+            QueryField::Collection => "collection_title",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryClause {
+    /// `None` searches every text field.
+    pub field: Option<QueryField>,
+    pub text: String,
+    pub phrase: bool,
+    pub excluded: bool,
+}
+
+/// Splits `query` into clauses. Errors report the offending token and its
+/// position, counted in characters.
+pub fn parse_query(query: &str) -> Result<Vec<QueryClause>, AppError> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut clauses = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        if chars[position].is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        let excluded = chars[position] == '-';
+        if excluded {
+            position += 1;
+        }
+
+        let mut field = None;
+        let word_start = position;
+        let word_end = scan(&chars, position, |c| {
+            c.is_whitespace() || c == ':' || c == '"'
+        });
+        if word_end > word_start && chars.get(word_end) == Some(&':') {
+            let name: String = chars[word_start..word_end].iter().collect();
+            field = QueryField::parse(&name);
+            if field.is_some() {
+                position = word_end + 1;
+            }
+        }
+
+        let (text, phrase) = if chars.get(position) == Some(&'"') {
+            let text_start = position + 1;
+            let text_end = scan(&chars, text_start, |c| c == '"');
+            if text_end == chars.len() {
+                let token: String = chars[start..].iter().collect();
+                return Err(AppError::invalid_query(
+                    &token,
+                    start,
+                    "Missing closing quote",
+                ));
+            }
+            position = text_end + 1;
+            (chars[text_start..text_end].iter().collect::<String>(), true)
+        } else {
+            let text_end = scan(&chars, position, |c| c.is_whitespace() || c == '"');
+            let text = chars[position..text_end].iter().collect::<String>();
+            position = text_end;
+            (text, false)
+        };
+
+        if text.trim().is_empty() {
+            if field.is_some() {
+                let token: String = chars[start..position].iter().collect();
+                return Err(AppError::invalid_query(&token, start, "Missing value"));
+            }
+            continue;
+        }
+        clauses.push(QueryClause {
+            field,
+            text,
+            phrase,
+            excluded,
+        });
+    }
+    Ok(clauses)
+}
+
+fn scan(chars: &[char], from: usize, stop: impl Fn(char) -> bool) -> usize {
+    chars[from..]
+        .iter()
+        .position(|c| stop(*c))
+        .map_or(chars.len(), |offset| from + offset)
+}
+
+#[cfg(test)]
+mod query_syntax_tests {
+    use crate::error::AppError;
+    use crate::search::query_syntax::{parse_query, QueryClause, QueryField};
+
+    fn clause(field: Option<QueryField>, text: &str, phrase: bool) -> QueryClause {
+        QueryClause {
+            field,
+            text: text.to_string(),
+            phrase,
+            excluded: false,
+        }
+    }
+
+    #[test]
+    fn parses_words_phrases_and_field_clauses() {
+        let clauses =
+            parse_query(r#"artist:interpol Style:"hard rock" tag:solo -live "the end""#).unwrap();
+
+        assert_eq!(
+            clauses,
+            vec![
+                clause(Some(QueryField::Artist), "interpol", false),
+                clause(Some(QueryField::Style), "hard rock", true),
+                clause(Some(QueryField::Tags), "solo", false),
+                QueryClause {
+                    excluded: true,
+                    ..clause(None, "live", false)
+                },
+                clause(None, "the end", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_prefixes_as_text() {
+        let clauses = parse_query("Re: Stacks intro:slow").unwrap();
+
+        assert_eq!(
+            clauses,
+            vec![
+                clause(None, "Re:", false),
+                clause(None, "Stacks", false),
+                clause(None, "intro:slow", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn points_errors_to_the_offending_token() {
+        let missing_quote = parse_query(r#"solo style:"hard rock"#).unwrap_err();
+        let missing_value = parse_query("song: solo").unwrap_err();
+
+        assert_eq!(
+            missing_quote,
+            AppError::invalid_query(r#"style:"hard rock"#, 5, "Missing closing quote")
+        );
+        assert_eq!(
+            missing_value,
+            AppError::invalid_query("song:", 0, "Missing value")
+        );
+    }
+}
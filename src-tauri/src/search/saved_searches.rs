@@ -0,0 +1,231 @@
+use crate::clock::clock;
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{read_json_or_recover, write_json_atomically};
+use crate::search::search_request::SearchRequest;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Number of past searches kept in the history.
+const HISTORY_SIZE: usize = 50;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub name: String,
+    pub request: SearchRequest,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedSearch {
+    pub fn new(name: &str, request: SearchRequest) -> Result<Self, AppError> {
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name: Self::valid_name(name)?,
+            request,
+            created_at: clock().now(),
+        })
+    }
+
+    pub fn rename(&mut self, name: &str) -> Result<(), AppError> {
+        self.name = Self::valid_name(name)?;
+        Ok(())
+    }
+
+    fn valid_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::invalid_input("A saved search needs a name"));
+        }
+        Ok(name.to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchHistoryEntry {
+    pub request: SearchRequest,
+    pub searched_at: DateTime<Utc>,
+}
+
+pub trait SavedSearchRepository: Send + Sync {
+    fn list(&self) -> Vec<SavedSearch>;
+    /// Adds `saved_search` or replaces the one with the same id.
+    fn add(&self, saved_search: SavedSearch) -> Result<(), AppError>;
+    fn delete(&self, id: &Uuid) -> Result<(), AppError>;
+    fn get_by_id(&self, id: &Uuid) -> Option<SavedSearch> {
+        self.list().into_iter().find(|s| s.id == *id)
+    }
+}
+
+/// Most recent searches first.
+pub trait SearchHistoryRepository: Send + Sync {
+    fn list(&self) -> Vec<SearchHistoryEntry>;
+    fn add(&self, entry: SearchHistoryEntry) -> Result<(), AppError>;
+}
+
+fn read_list<T: Serialize + DeserializeOwned>(path: &Path) -> Vec<T> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    read_json_or_recover(path).unwrap_or_else(|e| {
+        log::error!("Failed to read {path:?}: {e}");
+        Vec::new()
+    })
+}
+
+/// Puts `entry` on top of the history, removing an earlier run of the same
+/// search and the entries beyond [`HISTORY_SIZE`].
+fn push_entry(entries: &mut Vec<SearchHistoryEntry>, entry: SearchHistoryEntry) {
+    entries.retain(|e| e.request != entry.request);
+    entries.insert(0, entry);
+    entries.truncate(HISTORY_SIZE);
+}
+
+fn upsert(saved_searches: &mut Vec<SavedSearch>, saved_search: SavedSearch) {
+    match saved_searches.iter_mut().find(|s| s.id == saved_search.id) {
+        Some(existing) => *existing = saved_search,
+        None => saved_searches.push(saved_search),
+    }
+}
+
+fn remove(saved_searches: &mut Vec<SavedSearch>, id: &Uuid) -> Result<(), AppError> {
+    let count = saved_searches.len();
+    saved_searches.retain(|s| s.id != *id);
+    if saved_searches.len() == count {
+        return Err(AppError::not_found("Saved search", id));
+    }
+    Ok(())
+}
+
+pub struct SavedSearchRepositoryFile {
+    path: PathBuf,
+}
+
+impl SavedSearchRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            path: base_dir.join("saved-searches.json"),
+        }
+    }
+}
+
+impl SavedSearchRepository for SavedSearchRepositoryFile {
+    fn list(&self) -> Vec<SavedSearch> {
+        read_list(&self.path)
+    }
+
+    fn add(&self, saved_search: SavedSearch) -> Result<(), AppError> {
+        let mut saved_searches = self.list();
+        upsert(&mut saved_searches, saved_search);
+        write_json_atomically(&self.path, &saved_searches)
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let mut saved_searches = self.list();
+        remove(&mut saved_searches, id)?;
+        write_json_atomically(&self.path, &saved_searches)
+    }
+}
+
+#[derive(Default)]
+pub struct SavedSearchRepositoryMemory {
+    saved_searches: Mutex<Vec<SavedSearch>>,
+}
+
+impl SavedSearchRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SavedSearchRepository for SavedSearchRepositoryMemory {
+    fn list(&self) -> Vec<SavedSearch> {
+        self.saved_searches.lock().clone()
+    }
+
+    fn add(&self, saved_search: SavedSearch) -> Result<(), AppError> {
+        upsert(&mut self.saved_searches.lock(), saved_search);
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        remove(&mut self.saved_searches.lock(), id)
+    }
+}
+
+pub struct SearchHistoryRepositoryFile {
+    path: PathBuf,
+}
+
+impl SearchHistoryRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            path: base_dir.join("search-history.json"),
+        }
+    }
+}
+
+impl SearchHistoryRepository for SearchHistoryRepositoryFile {
+    fn list(&self) -> Vec<SearchHistoryEntry> {
+        read_list(&self.path)
+    }
+
+    fn add(&self, entry: SearchHistoryEntry) -> Result<(), AppError> {
+        let mut entries = self.list();
+        push_entry(&mut entries, entry);
+        write_json_atomically(&self.path, &entries)
+    }
+}
+
+#[derive(Default)]
+pub struct SearchHistoryRepositoryMemory {
+    entries: Mutex<Vec<SearchHistoryEntry>>,
+}
+
+impl SearchHistoryRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SearchHistoryRepository for SearchHistoryRepositoryMemory {
+    fn list(&self) -> Vec<SearchHistoryEntry> {
+        self.entries.lock().clone()
+    }
+
+    fn add(&self, entry: SearchHistoryEntry) -> Result<(), AppError> {
+        push_entry(&mut self.entries.lock(), entry);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod saved_searches_tests {
+    use crate::search::saved_searches::{
+        SavedSearch, SavedSearchRepository, SavedSearchRepositoryFile,
+    };
+    use crate::search::search_request::SearchRequest;
+    use std::fs;
+
+    #[test]
+    fn file_repository_persists_renames_and_deletions() {
+        let base_dir = std::env::temp_dir().join(format!("alexandria-{}", uuid::Uuid::new_v4()));
+        let repository = SavedSearchRepositoryFile::new(base_dir.clone());
+        let mut warmup = SavedSearch::new("Warm-up", SearchRequest::new("tag:warmup")).unwrap();
+        let blues = SavedSearch::new("Blues", SearchRequest::new("style:blues")).unwrap();
+        repository.add(warmup.clone()).unwrap();
+        repository.add(blues.clone()).unwrap();
+
+        warmup.rename("Daily warm-up").unwrap();
+        repository.add(warmup.clone()).unwrap();
+        repository.delete(&blues.id).unwrap();
+
+        let reopened = SavedSearchRepositoryFile::new(base_dir.clone());
+        assert_eq!(reopened.list(), vec![warmup]);
+        assert!(reopened.delete(&blues.id).is_err());
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
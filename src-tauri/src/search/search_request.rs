@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchRequest {
+    pub query: String,
+    #[serde(default)]
+    pub facets: FacetFilters,
+    #[serde(default)]
+    pub mode: SearchMode,
+    #[serde(default)]
+    pub filters: SearchFilters,
+    #[serde(default)]
+    pub sort: SortBy,
+    /// Overrides the natural order of `sort`, see [`SortBy::default_order`].
+    #[serde(default)]
+    pub order: Option<SortOrder>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Returns one hit per video instead of one per collection holding it.
+    #[serde(default)]
+    pub group_by_video: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SearchMode {
+    #[default]
+    Exact,
+    /// Tolerates typos, allowing more edits for longer terms.
+    Fuzzy,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SortBy {
+    #[default]
+    Relevance,
+    Artist,
+    Song,
+    Duration,
+    AddedAt,
+}
+
+impl SortBy {
+    /// Best matches and newest videos come first, names and durations ascend.
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            SortBy::Relevance | SortBy::AddedAt => SortOrder::Descending,
+            SortBy::Artist | SortBy::Song | SortBy::Duration => SortOrder::Ascending,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[cfg(test)]
+impl SearchRequest {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Restricts hits to videos within every given range. Bounds are inclusive.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchFilters {
+    #[serde(default)]
+    pub duration_seconds: Option<RangeFilter<u64>>,
+    #[serde(default)]
+    pub size_bytes: Option<RangeFilter<u64>>,
+    #[serde(default)]
+    pub added_at: Option<RangeFilter<DateTime<Utc>>>,
+    #[serde(default)]
+    pub collection_id: Option<Uuid>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RangeFilter<T> {
+    #[serde(default)]
+    pub min: Option<T>,
+    #[serde(default)]
+    pub max: Option<T>,
+}
+
+/// Selected facet values: a video matches when it has one of the selected
+/// values for every facet that has a selection.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FacetFilters {
+    #[serde(default)]
+    pub style: Vec<String>,
+    #[serde(default)]
+    pub artist: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub collection: Vec<Uuid>,
+}
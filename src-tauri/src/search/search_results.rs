@@ -0,0 +1,82 @@
+use crate::collections::collections::Video;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct SearchPage {
+    /// Set by the search service, see [`SearchStarted`].
+    pub search_id: Option<Uuid>,
+    pub hits: Vec<SearchHit>,
+    /// Number of matching documents, regardless of offset and limit.
+    pub total: usize,
+    pub facets: FacetCounts,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct SearchHit {
+    pub video: Video,
+    /// The collection of the matching document, or every collection holding
+    /// the video when hits are grouped by video.
+    pub collections: Vec<HitCollection>,
+    pub score: f32,
+    pub highlights: Vec<Highlight>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct HitCollection {
+    pub id: Uuid,
+    pub title: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct FacetCounts {
+    pub style: Vec<FacetCount>,
+    pub artist: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    pub collection: Vec<FacetCount>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Published when a search starts. A newer search supersedes the running ones:
+/// they stop and their results are dropped.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct SearchStarted {
+    pub search_id: Uuid,
+    pub query: String,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct SearchCompleted {
+    pub search_id: Uuid,
+    pub total: usize,
+    pub cancelled: bool,
+}
+
+/// A streamed hit, tagged with the search it belongs to.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct VideoSelected<'a> {
+    pub search_id: Uuid,
+    #[serde(flatten)]
+    pub video: &'a Video,
+}
+
+/// Library values starting with a typed prefix, most frequent first.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct Suggestions {
+    pub artist: Vec<FacetCount>,
+    pub song: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    pub style: Vec<FacetCount>,
+}
+
+/// A fragment of `field` where the byte ranges in `highlighted` matched the query.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Highlight {
+    pub field: String,
+    pub fragment: String,
+    pub highlighted: Vec<(usize, usize)>,
+}
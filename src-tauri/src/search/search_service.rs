@@ -1,21 +1,70 @@
-use crate::collections::collections::Video;
+use crate::clock::clock;
+use crate::collections::collections::{Collection, Video};
+use crate::collections::events::{CollectionDeleted, CollectionUpdated};
+use crate::error::AppError;
 use crate::event_bus::{Event, EventBusManager};
 use crate::repositories::repositories;
+use crate::search::query_syntax::parse_query;
+use crate::search::saved_searches::{
+    SavedSearch, SavedSearchRepository, SavedSearchRepositoryMemory, SearchHistoryEntry,
+    SearchHistoryRepository, SearchHistoryRepositoryMemory,
+};
+use crate::search::search_request::{SearchFilters, SearchMode, SearchRequest, SortBy, SortOrder};
+use crate::search::search_results::{
+    FacetCount, FacetCounts, Highlight, HitCollection, SearchCompleted, SearchHit, SearchPage,
+    SearchStarted, Suggestions, VideoSelected,
+};
+use crate::search::search_settings::{
+    AnalyzerSettings, FieldBoosts, SearchSettings, SearchSettingsRepository,
+    SearchSettingsRepositoryMemory,
+};
+use crate::search::smart_collections::{
+    SmartCollection, SmartCollectionRepository, SmartCollectionRepositoryMemory,
+};
+use crate::search::synonyms::{SynonymGroup, SynonymRepository, SynonymRepositoryMemory};
+use chrono::{DateTime, Utc};
 use parking_lot::{Mutex, MutexGuard, RwLock};
-use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tantivy::collector::{Collector, Count, SegmentCollector, TopDocs};
+use tantivy::columnar::{Column, StrColumn};
+use tantivy::directory::MmapDirectory;
+use tantivy::fastfield::FacetReader;
+use tantivy::query::{
+    AllQuery, BooleanQuery, BoostQuery, FuzzyTermQuery, MoreLikeThisQuery, Occur, PhraseQuery,
+    Query, RangeQuery, TermQuery,
+};
 use tantivy::schema::*;
-use tantivy::tokenizer::{Language, LowerCaser, Stemmer, TextAnalyzer};
-use tantivy::{Index, IndexWriter, ReloadPolicy};
+use tantivy::tokenizer::{AsciiFoldingFilter, LowerCaser, RawTokenizer, TextAnalyzer};
+use tantivy::{
+    DocAddress, DocId, Index, IndexWriter, ReloadPolicy, Score, SegmentReader, SnippetGenerator,
+    Term,
+};
+use uuid::Uuid;
 
-pub type ApplyPathRights = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+pub type ApplyPathRights = Box<dyn Fn(&Path) -> Result<(), AppError> + Send + Sync>;
+/// Tells a running search that a newer one superseded it.
+pub type IsCancelled = Arc<dyn Fn() -> bool + Send + Sync>;
 
 pub struct SearchService<T, U>
 where
     T: WriterCommit,
 {
     bus_manager: RwLock<Option<EventBusManager>>,
+    current_search: Arc<Mutex<Option<Uuid>>>,
     indexer: Box<dyn Indexer<T, U>>,
+    settings: Box<dyn SearchSettingsRepository>,
+    synonyms: Box<dyn SynonymRepository>,
+    saved_searches: Box<dyn SavedSearchRepository>,
+    history: Box<dyn SearchHistoryRepository>,
+    smart_collections: Box<dyn SmartCollectionRepository>,
+    /// Last known video ids of each smart collection.
+    smart_contents: Mutex<HashMap<Uuid, Vec<Uuid>>>,
 }
 
 pub trait Indexer<T, U>: Send + Sync
@@ -25,30 +74,119 @@ where
     fn initialize() -> Box<dyn Indexer<T, U>>
     where
         Self: Sized;
-    fn index_all_videos(&self) -> Result<(), String> {
+    fn index_all_videos(&self) -> Result<(), AppError> {
         let writer = self.retrieve_writer();
+        self.delete_all_videos(&writer);
         let collections = repositories().collections().list();
         for collection in collections {
             for video in collection.videos {
-                self.index_video(&video, &writer);
+                self.index_video(&video, &collection.id, &collection.title, &writer);
             }
         }
         writer.close()
     }
+    /// Applies `changes` with a single writer and commit.
+    fn apply_changes(&self, changes: &[IndexChange]) -> Result<(), AppError> {
+        let writer = self.retrieve_writer();
+        for change in changes {
+            match change {
+                IndexChange::VideoUpdated {
+                    video,
+                    collection_id,
+                } => {
+                    let collection_title = repositories()
+                        .collections()
+                        .get_by_id(collection_id)
+                        .map(|collection| collection.title)
+                        .unwrap_or_default();
+                    self.delete_video(&video.id, Some(collection_id), &writer);
+                    self.index_video(video, collection_id, &collection_title, &writer);
+                }
+                IndexChange::VideoRemoved {
+                    video_id,
+                    collection_id,
+                } => self.delete_video(video_id, Some(collection_id), &writer),
+                IndexChange::CollectionUpdated(collection) => {
+                    self.delete_collection(&collection.id, &writer);
+                    for video in &collection.videos {
+                        self.index_video(video, &collection.id, &collection.title, &writer);
+                    }
+                }
+                IndexChange::CollectionRemoved(collection_id) => {
+                    self.delete_collection(collection_id, &writer)
+                }
+            }
+        }
+        writer.close()
+    }
+    fn rebuild_required(&self) -> bool {
+        false
+    }
+    fn apply_settings(&self, _settings: &SearchSettings) -> Result<(), AppError> {
+        Ok(())
+    }
+    fn apply_synonyms(&self, _groups: Vec<SynonymGroup>) {}
     fn retrieve_writer(&self) -> Writer<'_, T, U>;
-    fn index_video(&self, video: &Video, writer: &Writer<'_, T, U>);
+    fn index_video(
+        &self,
+        video: &Video,
+        collection_id: &Uuid,
+        collection_title: &str,
+        writer: &Writer<'_, T, U>,
+    );
+    fn delete_video(
+        &self,
+        video_id: &Uuid,
+        collection_id: Option<&Uuid>,
+        writer: &Writer<'_, T, U>,
+    );
+    fn delete_collection(&self, collection_id: &Uuid, writer: &Writer<'_, T, U>);
+    fn delete_all_videos(&self, writer: &Writer<'_, T, U>);
+    /// Stops ranking documents once `is_cancelled` returns true, the
+    /// partial page is then meant to be dropped.
     fn search(
         &self,
-        query_str: &str,
-        bus_manager: &EventBusManager,
-        apply_path_rights: Option<&ApplyPathRights>,
-    ) -> Result<(), String>;
+        request: &SearchRequest,
+        is_cancelled: IsCancelled,
+    ) -> Result<SearchPage, AppError>;
+    fn suggest(&self, prefix: &str, limit: usize) -> Result<Suggestions, AppError>;
+    fn similar(&self, video_id: &Uuid, limit: usize) -> Result<Vec<SearchHit>, AppError>;
+}
+
+/// A library change to mirror in the index.
+pub enum IndexChange {
+    VideoUpdated { video: Video, collection_id: Uuid },
+    VideoRemoved { video_id: Uuid, collection_id: Uuid },
+    CollectionUpdated(Collection),
+    CollectionRemoved(Uuid),
 }
 
+/// Bump whenever the schema changes. The signature of an on-disk index combines
+/// it with the analyzer settings: an index written under another signature is
+/// discarded and rebuilt from the repositories.
+const INDEX_VERSION: &str = "10";
+const TOKENIZER: &str = "library_text";
+const EXACT_MATCH_BOOST: f32 = 2.0;
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+const TEXT_FIELDS: [&str; 5] = ["name", "artist", "song", "style", "tags"];
+const FACET_FIELDS: [&str; 4] = [
+    "style_facet",
+    "artist_facet",
+    "tags_facet",
+    "collection_facet",
+];
+const SIGNATURE_FILE: &str = "alexandria-signature";
+
 pub struct TantivyIndexer {
     index: Index,
     schema: Schema,
     writer: Mutex<IndexWriter>,
+    rebuild_required: AtomicBool,
+    analyzer: RwLock<AnalyzerSettings>,
+    boosts: RwLock<FieldBoosts>,
+    synonyms: RwLock<Vec<SynonymGroup>>,
+    index_dir: Option<PathBuf>,
 }
 
 struct Fields<T> {
@@ -57,7 +195,20 @@ struct Fields<T> {
     song: T,
     style: T,
     tags: T,
+    id: T,
     path: T,
+    collection_id: T,
+    collection_title: T,
+    style_facet: T,
+    artist_facet: T,
+    tags_facet: T,
+    collection_facet: T,
+    song_facet: T,
+    artist_sort: T,
+    song_sort: T,
+    duration_seconds: T,
+    size_bytes: T,
+    added_at: T,
 }
 
 pub(crate) struct Writer<'a, T, U> {
@@ -66,7 +217,7 @@ pub(crate) struct Writer<'a, T, U> {
 }
 
 impl<'a, T, U> Writer<'a, T, U> {
-    fn close(mut self) -> Result<(), String>
+    fn close(mut self) -> Result<(), AppError>
     where
         T: WriterCommit,
     {
@@ -75,20 +226,20 @@ impl<'a, T, U> Writer<'a, T, U> {
 }
 
 pub(crate) trait WriterCommit {
-    fn commit_all(&mut self) -> Result<(), String>;
+    fn commit_all(&mut self) -> Result<(), AppError>;
 }
 
 impl WriterCommit for IndexWriter {
-    fn commit_all(&mut self) -> Result<(), String> {
-        self.commit().map(|_| ()).map_err(|e| e.to_string())
+    fn commit_all(&mut self) -> Result<(), AppError> {
+        self.commit().map(|_| ()).map_err(AppError::from)
     }
 }
 
-impl Indexer<IndexWriter, Field> for TantivyIndexer {
-    fn initialize() -> Box<dyn Indexer<IndexWriter, Field>> {
+impl TantivyIndexer {
+    fn build_schema() -> Schema {
         let mut schema_builder = Schema::builder();
         let text_indexing = TextFieldIndexing::default()
-            .set_tokenizer("fr_stem")
+            .set_tokenizer(TOKENIZER)
             .set_index_option(IndexRecordOption::WithFreqsAndPositions);
         let text_options = TextOptions::default()
             .set_indexing_options(text_indexing)
@@ -98,50 +249,393 @@ impl Indexer<IndexWriter, Field> for TantivyIndexer {
         schema_builder.add_text_field("artist", text_options.clone());
         schema_builder.add_text_field("song", text_options.clone());
         schema_builder.add_text_field("style", text_options.clone());
-        schema_builder.add_text_field("tags", text_options);
-        schema_builder.add_text_field("path", STORED);
-
-        let schema = schema_builder.build();
-        let index = Index::create_in_ram(schema.clone());
+        schema_builder.add_text_field("tags", text_options.clone());
+        schema_builder.add_text_field("id", STRING | STORED | FAST);
+        schema_builder.add_text_field("path", STRING | FAST);
+        schema_builder.add_text_field("collection_id", STRING | STORED);
+        schema_builder.add_text_field("collection_title", text_options);
+        for facet_field in FACET_FIELDS {
+            schema_builder.add_facet_field(facet_field, FacetOptions::default());
+        }
+        schema_builder.add_facet_field("song_facet", FacetOptions::default());
+        schema_builder.add_text_field("artist_sort", FAST);
+        schema_builder.add_text_field("song_sort", FAST);
+        schema_builder.add_u64_field("duration_seconds", INDEXED | FAST | STORED);
+        schema_builder.add_u64_field("size_bytes", INDEXED | FAST | STORED);
+        schema_builder.add_date_field(
+            "added_at",
+            DateOptions::default()
+                .set_indexed()
+                .set_fast()
+                .set_stored()
+                .set_precision(DateTimePrecision::Seconds),
+        );
+        schema_builder.build()
+    }
 
-        let tokenizer = TextAnalyzer::builder(tantivy::tokenizer::SimpleTokenizer::default())
-            .filter(LowerCaser)
-            .filter(Stemmer::new(Language::French))
-            .build();
-        index.tokenizers().register("fr_stem", tokenizer);
+    fn from_index(
+        index: Index,
+        rebuild_required: bool,
+        settings: &SearchSettings,
+        index_dir: Option<PathBuf>,
+    ) -> Result<Self, AppError> {
+        index
+            .tokenizers()
+            .register(TOKENIZER, settings.analyzer.text_analyzer());
 
-        let writer = index
-            .writer(50_000_000)
-            .expect("Failed to create tantivy writer");
-        Box::new(Self {
+        let writer = index.writer(50_000_000)?;
+        Ok(Self {
+            schema: index.schema(),
             index,
-            schema,
             writer: Mutex::new(writer),
+            rebuild_required: AtomicBool::new(rebuild_required),
+            analyzer: RwLock::new(settings.analyzer.clone()),
+            boosts: RwLock::new(settings.boosts.clone()),
+            synonyms: RwLock::new(Vec::new()),
+            index_dir,
         })
     }
 
-    fn retrieve_writer(&self) -> Writer<'_, IndexWriter, Field> {
-        let mut index_writer = self.writer.lock();
+    fn signature(analyzer: &AnalyzerSettings) -> String {
+        format!(
+            "{INDEX_VERSION}:{}",
+            serde_json::to_string(analyzer).unwrap_or_default()
+        )
+    }
+
+    /// Opens the index stored in `index_dir`, creating it when missing. An index
+    /// written under another signature is wiped and flagged for a full rebuild.
+    pub fn open(
+        index_dir: &Path,
+        settings: &SearchSettings,
+    ) -> Result<Box<dyn Indexer<IndexWriter, Field>>, AppError> {
+        let signature = Self::signature(&settings.analyzer);
+        let signature_path = index_dir.join(SIGNATURE_FILE);
+        let up_to_date = fs::read_to_string(&signature_path).is_ok_and(|s| s == signature);
+        if !up_to_date && index_dir.exists() {
+            fs::remove_dir_all(index_dir).map_err(|e| AppError::io(index_dir, e))?;
+        }
+        fs::create_dir_all(index_dir).map_err(|e| AppError::io(index_dir, e))?;
+
+        let directory = MmapDirectory::open(index_dir).map_err(AppError::index)?;
+        let index = Index::open_or_create(directory, Self::build_schema())?;
+        fs::write(&signature_path, signature).map_err(|e| AppError::io(&signature_path, e))?;
+
+        let empty = index.reader()?.searcher().num_docs() == 0;
+        Ok(Box::new(Self::from_index(
+            index,
+            !up_to_date || empty,
+            settings,
+            Some(index_dir.to_path_buf()),
+        )?))
+    }
+
+    fn field(&self, name: &str) -> Field {
+        self.schema.get_field(name).unwrap()
+    }
+
+    /// Builds the query described by the [query syntax](crate::search::query_syntax).
+    fn text_query(
+        &self,
+        request: &SearchRequest,
+        fields: &[Field],
+    ) -> Result<Box<dyn Query>, AppError> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for clause in parse_query(&request.query)? {
+            let clause_fields = match clause.field {
+                Some(field) => vec![self.field(field.schema_name())],
+                None => fields.to_vec(),
+            };
+            let mut alternatives = Vec::new();
+            for text in std::iter::once(clause.text.clone()).chain(self.synonyms_of(&clause.text)) {
+                let query = match request.mode {
+                    SearchMode::Fuzzy if !clause.phrase => {
+                        self.fuzzy_query(&text, &clause_fields)?
+                    }
+                    _ => self.exact_query(&text, &clause_fields)?,
+                };
+                alternatives.extend(query.map(|query| (Occur::Should, query)));
+            }
+            let query = match alternatives.len() {
+                0 => None,
+                1 => alternatives.pop().map(|(_, query)| query),
+                _ => Some(Box::new(BooleanQuery::new(alternatives)) as Box<dyn Query>),
+            };
+            let occur = match (clause.excluded, clause.field) {
+                (true, _) => Occur::MustNot,
+                (false, Some(_)) => Occur::Must,
+                (false, None) => Occur::Should,
+            };
+            if let Some(query) = query {
+                clauses.push((occur, query));
+            }
+        }
+        if clauses.iter().all(|(occur, _)| *occur == Occur::MustNot) {
+            clauses.push((Occur::Must, Box::new(AllQuery)));
+        }
+        Ok(Box::new(BooleanQuery::new(clauses)))
+    }
+
+    /// Other terms of the synonym groups containing `text`.
+    fn synonyms_of(&self, text: &str) -> Vec<String> {
+        let text = normalize(text);
+        self.synonyms
+            .read()
+            .iter()
+            .filter(|group| group.terms.iter().any(|term| normalize(term) == text))
+            .flat_map(|group| group.terms.iter())
+            .filter(|term| normalize(term) != text)
+            .cloned()
+            .collect()
+    }
+
+    fn field_boost(&self, field: Field) -> f32 {
+        self.boosts
+            .read()
+            .for_field(self.schema.get_field_name(field))
+    }
+
+    /// Matches the analyzed `text` as a phrase in any of `fields`.
+    fn exact_query(
+        &self,
+        text: &str,
+        fields: &[Field],
+    ) -> Result<Option<Box<dyn Query>>, AppError> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for field in fields {
+            let mut analyzer = self.index.tokenizer_for_field(*field)?;
+            let mut tokens = analyzer.token_stream(text);
+            let mut terms = Vec::new();
+            while tokens.advance() {
+                let token = tokens.token();
+                terms.push((token.position, Term::from_field_text(*field, &token.text)));
+            }
+            let query: Box<dyn Query> = match terms.len() {
+                0 => continue,
+                1 => Box::new(TermQuery::new(
+                    terms.remove(0).1,
+                    IndexRecordOption::WithFreqs,
+                )),
+                _ => Box::new(PhraseQuery::new_with_offset(terms)),
+            };
+            let boost = self.field_boost(*field);
+            clauses.push((Occur::Should, Box::new(BoostQuery::new(query, boost))));
+        }
+        Ok((!clauses.is_empty()).then(|| Box::new(BooleanQuery::new(clauses)) as Box<dyn Query>))
+    }
+
+    /// Matches every analyzed term of `text` exactly or within an edit distance
+    /// that grows with the term length. Exact matches are boosted above typos.
+    fn fuzzy_query(
+        &self,
+        text: &str,
+        fields: &[Field],
+    ) -> Result<Option<Box<dyn Query>>, AppError> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for field in fields {
+            let boost = self.field_boost(*field);
+            let mut analyzer = self.index.tokenizer_for_field(*field)?;
+            let mut tokens = analyzer.token_stream(text);
+            while tokens.advance() {
+                let token = &tokens.token().text;
+                let term = Term::from_field_text(*field, token);
+                let exact = TermQuery::new(term.clone(), IndexRecordOption::WithFreqs);
+                clauses.push((
+                    Occur::Should,
+                    Box::new(BoostQuery::new(Box::new(exact), EXACT_MATCH_BOOST * boost)),
+                ));
+                let distance = fuzzy_distance(token);
+                if distance > 0 {
+                    let fuzzy = FuzzyTermQuery::new(term, distance, true);
+                    clauses.push((
+                        Occur::Should,
+                        Box::new(BoostQuery::new(Box::new(fuzzy), boost)),
+                    ));
+                }
+            }
+        }
+        Ok((!clauses.is_empty()).then(|| Box::new(BooleanQuery::new(clauses)) as Box<dyn Query>))
+    }
+
+    fn facet_filter(&self, field: &str, values: &[String]) -> Option<Box<dyn Query>> {
+        if values.is_empty() {
+            return None;
+        }
+        let field = self.field(field);
+        let terms = values
+            .iter()
+            .map(|value| {
+                let term = Term::from_facet(field, &Facet::from_path([value]));
+                (
+                    Occur::Should,
+                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn Query>,
+                )
+            })
+            .collect();
+        Some(Box::new(BooleanQuery::new(terms)))
+    }
+
+    fn range_filters(&self, filters: &SearchFilters) -> Vec<Box<dyn Query>> {
+        let mut queries: Vec<Box<dyn Query>> = Vec::new();
+        for (field, range) in [
+            ("duration_seconds", &filters.duration_seconds),
+            ("size_bytes", &filters.size_bytes),
+        ] {
+            if let Some(range) = range {
+                queries.push(Box::new(RangeQuery::new_u64_bounds(
+                    field.to_string(),
+                    range.min.map_or(Bound::Unbounded, Bound::Included),
+                    range.max.map_or(Bound::Unbounded, Bound::Included),
+                )));
+            }
+        }
+        if let Some(range) = &filters.added_at {
+            let to_date = |date: DateTime<Utc>| {
+                Bound::Included(tantivy::DateTime::from_timestamp_secs(date.timestamp()))
+            };
+            queries.push(Box::new(RangeQuery::new_date_bounds(
+                "added_at".to_string(),
+                range.min.map_or(Bound::Unbounded, to_date),
+                range.max.map_or(Bound::Unbounded, to_date),
+            )));
+        }
+        if let Some(collection_id) = filters.collection_id {
+            let term =
+                Term::from_field_text(self.field("collection_id"), &collection_id.to_string());
+            queries.push(Box::new(TermQuery::new(term, IndexRecordOption::Basic)));
+        }
+        queries
+    }
+
+    fn filtered_query(&self, query: Box<dyn Query>, request: &SearchRequest) -> Box<dyn Query> {
+        let facets = &request.facets;
+        let collections: Vec<String> = facets.collection.iter().map(Uuid::to_string).collect();
+        let filters: Vec<Box<dyn Query>> = [
+            self.facet_filter("style_facet", &facets.style),
+            self.facet_filter("artist_facet", &facets.artist),
+            self.facet_filter("tags_facet", &facets.tags),
+            self.facet_filter("collection_facet", &collections),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.range_filters(&request.filters))
+        .collect();
+        if filters.is_empty() {
+            return query;
+        }
+        Box::new(BooleanQuery::intersection(
+            std::iter::once(query).chain(filters).collect(),
+        ))
+    }
+
+    fn stored_uuid(&self, doc: &TantivyDocument, field: &str) -> Option<Uuid> {
+        doc.get_first(self.field(field))
+            .and_then(|v| v.as_str())
+            .and_then(|v| Uuid::parse_str(v).ok())
+    }
+
+    fn stored_collection(&self, doc: &TantivyDocument) -> Option<HitCollection> {
+        Some(HitCollection {
+            id: self.stored_uuid(doc, "collection_id")?,
+            title: doc
+                .get_first(self.field("collection_title"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+
+    /// Reads the video of a stored document from its collection, caching the
+    /// collections already loaded.
+    fn stored_video(
+        &self,
+        doc: &TantivyDocument,
+        collections: &mut HashMap<Uuid, Option<Collection>>,
+    ) -> Option<(Video, HitCollection)> {
+        let video_id = self.stored_uuid(doc, "id")?;
+        let collection = self.stored_collection(doc)?;
+        let video = collections
+            .entry(collection.id)
+            .or_insert_with(|| repositories().collections().get_by_id(&collection.id))
+            .as_ref()?
+            .videos
+            .iter()
+            .find(|v| v.id == video_id)?
+            .clone();
+        Some((video, collection))
+    }
+}
+
+impl Indexer<IndexWriter, Field> for TantivyIndexer {
+    fn initialize() -> Box<dyn Indexer<IndexWriter, Field>> {
+        let index = Index::create_in_ram(Self::build_schema());
+        Box::new(
+            Self::from_index(index, true, &SearchSettings::default(), None)
+                .expect("Failed to create tantivy writer"),
+        )
+    }
+
+    fn rebuild_required(&self) -> bool {
+        self.rebuild_required.load(Ordering::SeqCst)
+    }
 
-        let _ = index_writer
-            .delete_all_documents()
-            .map_err(|e| e.to_string());
-        let _ = index_writer.commit().map_err(|e| e.to_string());
+    fn apply_settings(&self, settings: &SearchSettings) -> Result<(), AppError> {
+        *self.boosts.write() = settings.boosts.clone();
+        if *self.analyzer.read() == settings.analyzer {
+            return Ok(());
+        }
+        self.index
+            .tokenizers()
+            .register(TOKENIZER, settings.analyzer.text_analyzer());
+        *self.analyzer.write() = settings.analyzer.clone();
+        self.index_all_videos()?;
+
+        if let Some(index_dir) = &self.index_dir {
+            let signature_path = index_dir.join(SIGNATURE_FILE);
+            fs::write(&signature_path, Self::signature(&settings.analyzer))
+                .map_err(|e| AppError::io(&signature_path, e))?;
+        }
+        Ok(())
+    }
+
+    fn apply_synonyms(&self, groups: Vec<SynonymGroup>) {
+        *self.synonyms.write() = groups;
+    }
 
+    fn retrieve_writer(&self) -> Writer<'_, IndexWriter, Field> {
         Writer {
-            writer: index_writer,
+            writer: self.writer.lock(),
             fields: Fields {
-                name: self.schema.get_field("name").unwrap(),
-                artist: self.schema.get_field("artist").unwrap(),
-                song: self.schema.get_field("song").unwrap(),
-                style: self.schema.get_field("style").unwrap(),
-                tags: self.schema.get_field("tags").unwrap(),
-                path: self.schema.get_field("path").unwrap(),
+                name: self.field("name"),
+                artist: self.field("artist"),
+                song: self.field("song"),
+                style: self.field("style"),
+                tags: self.field("tags"),
+                id: self.field("id"),
+                path: self.field("path"),
+                collection_id: self.field("collection_id"),
+                collection_title: self.field("collection_title"),
+                style_facet: self.field("style_facet"),
+                artist_facet: self.field("artist_facet"),
+                tags_facet: self.field("tags_facet"),
+                collection_facet: self.field("collection_facet"),
+                song_facet: self.field("song_facet"),
+                artist_sort: self.field("artist_sort"),
+                song_sort: self.field("song_sort"),
+                duration_seconds: self.field("duration_seconds"),
+                size_bytes: self.field("size_bytes"),
+                added_at: self.field("added_at"),
             },
         }
     }
 
-    fn index_video(&self, video: &Video, writer: &Writer<'_, IndexWriter, Field>) {
+    fn index_video(
+        &self,
+        video: &Video,
+        collection_id: &Uuid,
+        collection_title: &str,
+        writer: &Writer<'_, IndexWriter, Field>,
+    ) {
         let fields = &writer.fields;
         let mut doc = TantivyDocument::default();
         doc.add_text(fields.name, &video.name);
@@ -151,227 +645,2278 @@ impl Indexer<IndexWriter, Field> for TantivyIndexer {
         let styles_str = video
             .style
             .iter()
-            .map(|s| format!("{:?}", s))
+            .flat_map(|s| std::iter::once(s.display_name()).chain(s.aliases().iter().copied()))
             .collect::<Vec<_>>()
             .join(" ");
         doc.add_text(fields.style, &styles_str);
 
         doc.add_text(fields.tags, &video.tags.join(" "));
-        doc.add_text(fields.path, &video.path.to_string_lossy());
+        doc.add_text(fields.id, video.id.to_string());
+        doc.add_text(fields.path, video.path.to_string_lossy());
+        doc.add_text(fields.collection_id, collection_id.to_string());
+        doc.add_text(fields.collection_title, collection_title);
+
+        video
+            .style
+            .iter()
+            .for_each(|s| doc.add_facet(fields.style_facet, Facet::from_path([s.display_name()])));
+        if !video.artist.is_empty() {
+            doc.add_facet(fields.artist_facet, Facet::from_path([&video.artist]));
+            doc.add_text(fields.artist_sort, normalize(&video.artist));
+        }
+        if !video.song.is_empty() {
+            doc.add_facet(fields.song_facet, Facet::from_path([&video.song]));
+            doc.add_text(fields.song_sort, normalize(&video.song));
+        }
+        video
+            .tags
+            .iter()
+            .for_each(|tag| doc.add_facet(fields.tags_facet, Facet::from_path([tag])));
+        doc.add_facet(
+            fields.collection_facet,
+            Facet::from_path([collection_id.to_string()]),
+        );
+        doc.add_u64(fields.duration_seconds, video.duration_seconds);
+        doc.add_u64(fields.size_bytes, video.size_bytes);
+        if let Some(added_at) = video.added_at {
+            doc.add_date(
+                fields.added_at,
+                tantivy::DateTime::from_timestamp_secs(added_at.timestamp()),
+            );
+        }
+
+        if let Err(e) = writer.writer.add_document(doc) {
+            log::error!("Failed to index video {}: {e}", video.id);
+        }
+    }
+
+    fn delete_video(
+        &self,
+        video_id: &Uuid,
+        collection_id: Option<&Uuid>,
+        writer: &Writer<'_, IndexWriter, Field>,
+    ) {
+        let video_term = Term::from_field_text(writer.fields.id, &video_id.to_string());
+        let Some(collection_id) = collection_id else {
+            writer.writer.delete_term(video_term);
+            return;
+        };
+        let collection_term =
+            Term::from_field_text(writer.fields.collection_id, &collection_id.to_string());
+        let query = BooleanQuery::intersection(vec![
+            Box::new(TermQuery::new(video_term, IndexRecordOption::Basic)),
+            Box::new(TermQuery::new(collection_term, IndexRecordOption::Basic)),
+        ]);
+        if let Err(e) = writer.writer.delete_query(Box::new(query)) {
+            log::error!("Failed to remove video {video_id} from the index: {e}");
+        }
+    }
 
-        writer
-            .writer
-            .add_document(doc)
-            .map_err(|e| e.to_string())
-            .ok();
+    fn delete_collection(&self, collection_id: &Uuid, writer: &Writer<'_, IndexWriter, Field>) {
+        writer.writer.delete_term(Term::from_field_text(
+            writer.fields.collection_id,
+            &collection_id.to_string(),
+        ));
+    }
+
+    fn delete_all_videos(&self, writer: &Writer<'_, IndexWriter, Field>) {
+        if let Err(e) = writer.writer.delete_all_documents() {
+            log::error!("Failed to clear the search index: {e}");
+        }
+        self.rebuild_required.store(false, Ordering::SeqCst);
     }
 
     fn search(
         &self,
-        query_str: &str,
-        event_bus_manager: &EventBusManager,
-        apply_path_rights: Option<&ApplyPathRights>,
-    ) -> Result<(), String> {
+        request: &SearchRequest,
+        is_cancelled: IsCancelled,
+    ) -> Result<SearchPage, AppError> {
         let reader = self
             .index
             .reader_builder()
             .reload_policy(ReloadPolicy::Manual)
-            .try_into()
-            .map_err(|e| e.to_string())?;
+            .try_into()?;
 
-        reader.reload().map_err(|e| e.to_string())?;
+        reader.reload()?;
         let searcher = reader.searcher();
 
-        let name = self.schema.get_field("name").unwrap();
-        let artist = self.schema.get_field("artist").unwrap();
-        let song = self.schema.get_field("song").unwrap();
-        let style = self.schema.get_field("style").unwrap();
-        let tags = self.schema.get_field("tags").unwrap();
+        let name = self.field("name");
+        let artist = self.field("artist");
+        let song = self.field("song");
+        let style = self.field("style");
+        let tags = self.field("tags");
 
-        let query_parser =
-            QueryParser::for_index(&self.index, vec![name, artist, song, style, tags]);
-        let query = query_parser
-            .parse_query(query_str)
-            .map_err(|e| e.to_string())?;
+        let text_fields = vec![name, artist, song, style, tags];
+        let query = self.text_query(request, &text_fields)?;
+        let query = self.filtered_query(query, request);
 
-        let top_docs = searcher
-            .search(&query, &TopDocs::with_limit(50))
-            .map_err(|e| e.to_string())?;
+        let facet_collectors = FACET_FIELDS.map(|field| VideoFacetCollector { field });
+        let [style_collector, artist_collector, tags_collector, collection_collector] =
+            facet_collectors;
+        let sort = request.sort;
+        let order = request.order.unwrap_or(sort.default_order());
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .min(MAX_PAGE_SIZE);
+        let num_docs = searcher.num_docs() as usize;
+        let grouped = request.group_by_video;
+        // Grouping ranks every match to count and page distinct videos. Pages
+        // past the last match are empty, without sizing the collector after them.
+        let (window, offset) = match grouped {
+            true => (num_docs, 0),
+            false => (limit, request.offset.min(num_docs)),
+        };
+        let top_docs_collector = TopDocs::with_limit(window.max(1))
+            .and_offset(offset)
+            .tweak_score(move |segment_reader: &SegmentReader| {
+                let column = SortColumn::open(segment_reader, sort);
+                let paths = grouped
+                    .then(|| segment_reader.fast_fields().str("path").ok().flatten())
+                    .flatten();
+                move |doc: DocId, score: Score| {
+                    let path = paths.as_ref().and_then(|paths| first_str(paths, doc));
+                    (column.key(doc, order), score, path)
+                }
+            });
+        let (top_docs, count, (style_counts, artist_counts, tags_counts, collection_counts)) =
+            searcher.search(
+                &query,
+                &Cancellable {
+                    inner: (
+                        top_docs_collector,
+                        Count,
+                        (
+                            style_collector,
+                            artist_collector,
+                            tags_collector,
+                            collection_collector,
+                        ),
+                    ),
+                    is_cancelled,
+                },
+            )?;
+        let facets = FacetCounts {
+            style: facet_counts(style_counts),
+            artist: facet_counts(artist_counts),
+            tags: facet_counts(tags_counts),
+            collection: facet_counts(collection_counts),
+        };
 
-        let collections = repositories().collections().list();
+        let snippet_generators = [name, artist, song, style, tags]
+            .into_iter()
+            .map(|field| {
+                let generator = SnippetGenerator::create(&searcher, &*query, field)?;
+                Ok((self.schema.get_field_name(field).to_string(), generator))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
 
-        for (_score, doc_address) in top_docs {
-            let retrieved_doc = searcher
-                .doc::<TantivyDocument>(doc_address)
-                .map_err(|e| e.to_string())?;
-            let path_field = self.schema.get_field("path").unwrap();
-
-            if let Some(path_value) = retrieved_doc.get_first(path_field).and_then(|v| v.as_str()) {
-                for collection in &collections {
-                    if let Some(video) = collection
-                        .videos
-                        .iter()
-                        .find(|v| v.path.to_string_lossy() == path_value)
-                    {
-                        if let Some(callback) = apply_path_rights {
-                            callback(video.path.to_str().unwrap())?;
-                        }
-                        event_bus_manager.event_bus.publish(Event {
-                            event_type: "video:selected".parse().unwrap(),
-                            data: serde_json::to_value(video.clone()).unwrap(),
-                        });
-                        break;
-                    }
+        // Documents of each returned video, best ranked first. Entries of the
+        // same file in several collections have their own ids but share a path.
+        let mut groups: Vec<Vec<(Score, DocAddress)>> = Vec::new();
+        let total = if grouped {
+            let mut positions: HashMap<String, usize> = HashMap::new();
+            for ((_, score, path), doc_address) in top_docs {
+                let position = *positions
+                    .entry(path.unwrap_or_default())
+                    .or_insert_with(|| {
+                        groups.push(Vec::new());
+                        groups.len() - 1
+                    });
+                groups[position].push((score, doc_address));
+            }
+            let total = groups.len();
+            groups = groups
+                .into_iter()
+                .skip(request.offset)
+                .take(limit)
+                .collect();
+            total
+        } else {
+            groups = top_docs
+                .into_iter()
+                .take(limit)
+                .map(|((_, score, _), doc_address)| vec![(score, doc_address)])
+                .collect();
+            count
+        };
+
+        let mut collections: HashMap<Uuid, Option<Collection>> = HashMap::new();
+        let mut hits = Vec::new();
+        for group in groups {
+            let (score, doc_address) = group[0];
+            let retrieved_doc = searcher.doc::<TantivyDocument>(doc_address)?;
+            let Some((video, collection)) = self.stored_video(&retrieved_doc, &mut collections)
+            else {
+                continue;
+            };
+            let mut hit_collections = vec![collection];
+            for (_, other_address) in &group[1..] {
+                let other_doc = searcher.doc::<TantivyDocument>(*other_address)?;
+                hit_collections.extend(self.stored_collection(&other_doc));
+            }
+
+            let highlights = snippet_generators
+                .iter()
+                .filter_map(|(field, generator)| {
+                    let snippet = generator.snippet_from_doc(&retrieved_doc);
+                    (!snippet.highlighted().is_empty()).then(|| Highlight {
+                        field: field.clone(),
+                        fragment: snippet.fragment().to_string(),
+                        highlighted: snippet
+                            .highlighted()
+                            .iter()
+                            .map(|range| (range.start, range.end))
+                            .collect(),
+                    })
+                })
+                .collect();
+            hits.push(SearchHit {
+                video,
+                collections: hit_collections,
+                score,
+                highlights,
+            });
+        }
+        Ok(SearchPage {
+            search_id: None,
+            hits,
+            total,
+            facets,
+        })
+    }
+
+    /// Ranks the videos sharing terms with `video_id`, leaving out the video
+    /// itself and duplicates of its file. A video held by several collections
+    /// is returned once, listing them all.
+    fn similar(&self, video_id: &Uuid, limit: usize) -> Result<Vec<SearchHit>, AppError> {
+        let searcher = self.index.reader()?.searcher();
+        let id_term = Term::from_field_text(self.field("id"), &video_id.to_string());
+        let id_query = TermQuery::new(id_term, IndexRecordOption::Basic);
+        let Some((_, source_address)) = searcher
+            .search(&id_query, &TopDocs::with_limit(1))?
+            .into_iter()
+            .next()
+        else {
+            return Err(AppError::not_found("Video", video_id));
+        };
+        let source_doc = searcher.doc::<TantivyDocument>(source_address)?;
+        let mut collections = HashMap::new();
+        let source_path = self
+            .stored_video(&source_doc, &mut collections)
+            .map(|(video, _)| video.path);
+
+        let document_fields = TEXT_FIELDS
+            .iter()
+            .map(|name| {
+                let field = self.field(name);
+                (field, source_doc.get_all(field).cloned().collect())
+            })
+            .collect();
+        let more_like_this = MoreLikeThisQuery::builder()
+            .with_min_doc_frequency(2)
+            .with_min_term_frequency(1)
+            .with_document_fields(document_fields);
+        let query = BooleanQuery::new(vec![
+            (Occur::Must, Box::new(more_like_this) as Box<dyn Query>),
+            (Occur::MustNot, Box::new(id_query)),
+        ]);
+
+        // A file has one document per collection holding it, so documents are
+        // fetched page by page until `limit` distinct files are found.
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+        let window = limit.saturating_mul(3);
+        let mut hits: Vec<SearchHit> = Vec::new();
+        let mut offset = 0;
+        loop {
+            let top_docs =
+                searcher.search(&query, &TopDocs::with_limit(window).and_offset(offset))?;
+            let fetched = top_docs.len();
+            for (score, doc_address) in top_docs {
+                let retrieved_doc = searcher.doc::<TantivyDocument>(doc_address)?;
+                let Some((video, collection)) = self.stored_video(&retrieved_doc, &mut collections)
+                else {
+                    continue;
+                };
+                if Some(&video.path) == source_path.as_ref() {
+                    continue;
                 }
+                if let Some(hit) = hits.iter_mut().find(|hit| hit.video.path == video.path) {
+                    hit.collections.push(collection);
+                } else if hits.len() < limit {
+                    hits.push(SearchHit {
+                        video,
+                        collections: vec![collection],
+                        score,
+                        highlights: Vec::new(),
+                    });
+                }
+            }
+            if fetched < window || hits.len() >= limit {
+                break;
             }
+            offset += window;
         }
-        Ok(())
+        Ok(hits)
     }
-}
 
-impl<T, U> SearchService<T, U>
-where
-    T: WriterCommit,
-{
-    pub fn new(indexer: Box<dyn Indexer<T, U>>) -> Self {
-        Self {
-            bus_manager: RwLock::new(None),
-            indexer,
+    fn suggest(&self, prefix: &str, limit: usize) -> Result<Suggestions, AppError> {
+        let prefix = normalize(prefix);
+        if prefix.is_empty() {
+            return Ok(Suggestions::default());
         }
+        let searcher = self.index.reader()?.searcher();
+        let suggestions_for = |field: &'static str| -> Result<Vec<FacetCount>, AppError> {
+            let counts = searcher.search(&AllQuery, &VideoFacetCollector { field })?;
+            Ok(facet_counts(counts)
+                .into_iter()
+                .filter(|suggestion| matches_prefix(&suggestion.value, &prefix))
+                .take(limit)
+                .collect())
+        };
+        Ok(Suggestions {
+            artist: suggestions_for("artist_facet")?,
+            song: suggestions_for("song_facet")?,
+            tags: suggestions_for("tags_facet")?,
+            style: suggestions_for("style_facet")?,
+        })
     }
+}
 
-    pub fn initialize(&self, bus_manager: EventBusManager) {
-        *self.bus_manager.write() = Some(bus_manager);
+/// Ids of the matching videos for each value of a facet field. A video has
+/// one document per collection holding it but counts once per value.
+struct VideoFacetCollector {
+    field: &'static str,
+}
+
+impl Collector for VideoFacetCollector {
+    type Fruit = HashMap<String, HashSet<String>>;
+    type Child = VideoFacetSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: u32,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        Ok(VideoFacetSegmentCollector {
+            facets: segment.facet_reader(self.field)?,
+            ids: segment.fast_fields().str("id")?,
+            videos_by_ord: HashMap::new(),
+        })
     }
 
-    pub fn index_all_videos(&self) -> Result<(), String> {
-        self.indexer.index_all_videos()
+    fn requires_scoring(&self) -> bool {
+        false
     }
 
-    pub fn search(
+    fn merge_fruits(
         &self,
-        query_str: &str,
-        allow_path: Option<&ApplyPathRights>,
-    ) -> Result<(), String> {
-        if let Some(bus_manager) = &*self.bus_manager.read() {
-            return self.indexer.search(query_str, bus_manager, allow_path);
+        segment_fruits: Vec<HashMap<String, HashSet<String>>>,
+    ) -> tantivy::Result<Self::Fruit> {
+        let mut merged: HashMap<String, HashSet<String>> = HashMap::new();
+        for fruit in segment_fruits {
+            for (value, videos) in fruit {
+                merged.entry(value).or_default().extend(videos);
+            }
         }
-        Ok(())
+        Ok(merged)
     }
 }
 
-#[cfg(test)]
-mod search_service_setup {
-    use crate::collections::collections::CollectionRepositoryMemory;
-    use crate::infra::event_bus::memory_event_bus::MemoryEventBus;
-    use crate::repositories::{with_test_repositories, Repositories, RepositoriesGuard};
-    use std::sync::Arc;
+struct VideoFacetSegmentCollector {
+    facets: FacetReader,
+    ids: Option<StrColumn>,
+    /// Video id ordinals for each facet ordinal of the segment.
+    videos_by_ord: HashMap<u64, HashSet<u64>>,
+}
 
-    pub fn setup() -> (Arc<MemoryEventBus>, RepositoriesGuard) {
-        let mem = CollectionRepositoryMemory::new();
-        let _repos_guard = with_test_repositories(Repositories::new(Arc::new(mem)));
-        let event_bus = Arc::new(MemoryEventBus::new());
+impl SegmentCollector for VideoFacetSegmentCollector {
+    type Fruit = HashMap<String, HashSet<String>>;
 
-        (event_bus, _repos_guard)
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let Some(id_ord) = self.ids.as_ref().and_then(|ids| ids.term_ords(doc).next()) else {
+            return;
+        };
+        for facet_ord in self.facets.facet_ords(doc) {
+            self.videos_by_ord
+                .entry(facet_ord)
+                .or_default()
+                .insert(id_ord);
+        }
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        let mut videos_by_value: HashMap<String, HashSet<String>> = HashMap::new();
+        let Some(ids) = &self.ids else {
+            return videos_by_value;
+        };
+        for (facet_ord, id_ords) in self.videos_by_ord {
+            let mut encoded = Vec::new();
+            if !matches!(
+                self.facets
+                    .facet_dict()
+                    .ord_to_term(facet_ord, &mut encoded),
+                Ok(true)
+            ) {
+                continue;
+            }
+            let Some(value) = Facet::from_encoded(encoded)
+                .ok()
+                .and_then(|facet| facet.to_path().last().map(|value| value.to_string()))
+            else {
+                continue;
+            };
+            let videos = videos_by_value.entry(value).or_default();
+            for id_ord in id_ords {
+                let mut id = String::new();
+                if ids.ord_to_str(id_ord, &mut id).is_ok() {
+                    videos.insert(id);
+                }
+            }
+        }
+        videos_by_value
     }
 }
 
-#[cfg(test)]
-mod search_service_test {
-    use crate::collections::collection_builder::{a_collection, a_video};
-    use crate::collections::collections::Video;
-    use crate::event_bus::EventBusManager;
-    use crate::repositories::repositories;
-    use crate::search::search_service::search_service_setup::setup;
-    use crate::search::search_service::{
-        ApplyPathRights, Fields, Indexer, SearchService, WriterCommit,
-    };
-    use parking_lot::Mutex;
-    use std::sync::Arc;
+/// Stops feeding `inner` once the search is cancelled, so a superseded
+/// search quits ranking the remaining documents.
+struct Cancellable<C> {
+    inner: C,
+    is_cancelled: IsCancelled,
+}
 
-    struct DummyWriter;
-    impl WriterCommit for DummyWriter {
-        fn commit_all(&mut self) -> Result<(), String> {
-            Ok(())
-        }
+/// Documents collected between two cancellation checks.
+const CANCELLATION_CHECK_INTERVAL: u32 = 1024;
+
+impl<C: Collector> Collector for Cancellable<C> {
+    type Fruit = C::Fruit;
+    type Child = CancellableSegment<C::Child>;
+
+    fn for_segment(
+        &self,
+        segment_local_id: u32,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        Ok(CancellableSegment {
+            inner: self.inner.for_segment(segment_local_id, segment)?,
+            is_cancelled: self.is_cancelled.clone(),
+            collected: 0,
+            cancelled: (self.is_cancelled)(),
+        })
     }
 
-    struct DummyIndexer {
-        pub indexed_videos: Arc<Mutex<Vec<Video>>>,
-        pub writer: Mutex<DummyWriter>,
+    fn requires_scoring(&self) -> bool {
+        self.inner.requires_scoring()
     }
 
-    impl Indexer<DummyWriter, String> for DummyIndexer {
-        fn initialize() -> Box<dyn Indexer<DummyWriter, String>>
-        where
-            Self: Sized,
-        {
-            Box::new(DummyIndexer {
-                indexed_videos: Arc::new(Mutex::new(vec![])),
-                writer: Mutex::new(DummyWriter),
-            })
-        }
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> tantivy::Result<Self::Fruit> {
+        self.inner.merge_fruits(segment_fruits)
+    }
+}
 
-        fn retrieve_writer(
-            &self,
-        ) -> crate::search::search_service::Writer<'_, DummyWriter, String> {
-            crate::search::search_service::Writer {
-                writer: self.writer.lock(),
-                fields: Fields {
-                    name: "name".to_string(),
-                    artist: "artist".to_string(),
-                    song: "song".to_string(),
-                    style: "style".to_string(),
-                    tags: "tags".to_string(),
-                    path: "path".to_string(),
-                },
-            }
-        }
+struct CancellableSegment<S> {
+    inner: S,
+    is_cancelled: IsCancelled,
+    collected: u32,
+    cancelled: bool,
+}
 
-        fn index_video(
-            &self,
-            _video: &Video,
-            _writer: &crate::search::search_service::Writer<'_, DummyWriter, String>,
-        ) {
-            let mut indexed_videos = self.indexed_videos.lock();
-            indexed_videos.push(_video.clone());
-        }
+impl<S: SegmentCollector> SegmentCollector for CancellableSegment<S> {
+    type Fruit = S::Fruit;
 
-        fn search(
-            &self,
-            _query_str: &str,
-            _bus_manager: &EventBusManager,
-            _apply_path_rights: Option<&ApplyPathRights>,
-        ) -> Result<(), String> {
-            todo!()
+    fn collect(&mut self, doc: DocId, score: Score) {
+        if self.cancelled {
+            return;
+        }
+        self.collected = self.collected.wrapping_add(1);
+        if self.collected.is_multiple_of(CANCELLATION_CHECK_INTERVAL) && (self.is_cancelled)() {
+            self.cancelled = true;
+            return;
         }
+        self.inner.collect(doc, score);
     }
 
-    #[test]
-    fn index_all_videos() {
-        let (_event_bus, _repositories_guard) = setup();
-        let indexed_videos = Arc::new(Mutex::new(vec![]));
-        let indexer = Box::new(DummyIndexer {
-            indexed_videos: indexed_videos.clone(),
-            writer: Mutex::new(DummyWriter),
-        });
-        let search_service = SearchService::new(indexer);
-        repositories().collections().add(
-            a_collection()
-                .with_title("Collection 1")
-                .add_video(&a_video().build())
-                .add_video(&a_video().build())
-                .build(),
-        );
-        repositories().collections().add(
-            a_collection()
-                .with_title("Collection 2")
-                .add_video(&a_video().build())
-                .build(),
-        );
+    fn harvest(self) -> Self::Fruit {
+        self.inner.harvest()
+    }
+}
 
-        search_service.index_all_videos().unwrap();
+/// Fast field values a segment is sorted on.
+enum SortColumn {
+    Relevance,
+    Number(Column<u64>),
+    Date(Column<tantivy::DateTime>),
+    Text(StrColumn),
+}
 
-        assert_eq!(indexed_videos.lock().len(), 3);
+#[derive(Clone, PartialEq, PartialOrd)]
+enum SortValue {
+    Number(u64),
+    Date(tantivy::DateTime),
+    Text(String),
+}
+
+/// Top docs keep the greatest keys, so ascending values are reversed.
+/// Documents without a value have no key and come last in both orders.
+#[derive(Clone, PartialEq, PartialOrd)]
+enum SortKey {
+    Ascending(Reverse<SortValue>),
+    Descending(SortValue),
+}
+
+impl SortColumn {
+    fn open(segment_reader: &SegmentReader, sort: SortBy) -> Self {
+        let fast_fields = segment_reader.fast_fields();
+        let column = match sort {
+            SortBy::Relevance => return SortColumn::Relevance,
+            SortBy::Duration => fast_fields.u64("duration_seconds").map(SortColumn::Number),
+            SortBy::AddedAt => fast_fields.date("added_at").map(SortColumn::Date),
+            SortBy::Artist => fast_fields
+                .str("artist_sort")
+                .map(|column| column.map_or(SortColumn::Relevance, SortColumn::Text)),
+            SortBy::Song => fast_fields
+                .str("song_sort")
+                .map(|column| column.map_or(SortColumn::Relevance, SortColumn::Text)),
+        };
+        column.unwrap_or_else(|e| {
+            log::error!("Failed to open sort column for {sort:?}: {e}");
+            SortColumn::Relevance
+        })
+    }
+
+    fn key(&self, doc: DocId, order: SortOrder) -> Option<SortKey> {
+        let value = match self {
+            SortColumn::Relevance => None,
+            SortColumn::Number(column) => column.first(doc).map(SortValue::Number),
+            SortColumn::Date(column) => column.first(doc).map(SortValue::Date),
+            SortColumn::Text(column) => first_str(column, doc).map(SortValue::Text),
+        }?;
+        Some(match order {
+            SortOrder::Ascending => SortKey::Ascending(Reverse(value)),
+            SortOrder::Descending => SortKey::Descending(value),
+        })
+    }
+}
+
+fn first_str(column: &StrColumn, doc: DocId) -> Option<String> {
+    let ord = column.term_ords(doc).next()?;
+    let mut text = String::new();
+    column.ord_to_str(ord, &mut text).ok()?;
+    Some(text)
+}
+
+fn normalize(text: &str) -> String {
+    let mut analyzer = TextAnalyzer::builder(RawTokenizer::default())
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .build();
+    let mut tokens = analyzer.token_stream(text.trim());
+    let mut normalized = String::new();
+    while tokens.advance() {
+        normalized.push_str(&tokens.token().text);
+    }
+    normalized
+}
+
+/// Whether `value` or one of its words starts with the normalized `prefix`.
+fn matches_prefix(value: &str, prefix: &str) -> bool {
+    let value = normalize(value);
+    value.starts_with(prefix)
+        || value
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word.starts_with(prefix))
+}
+
+fn fuzzy_distance(term: &str) -> u8 {
+    match term.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+fn facet_counts(videos_by_value: HashMap<String, HashSet<String>>) -> Vec<FacetCount> {
+    let mut counts: Vec<FacetCount> = videos_by_value
+        .into_iter()
+        .map(|(value, videos)| FacetCount {
+            value,
+            count: videos.len() as u64,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts
+}
+
+impl<T, U> SearchService<T, U>
+where
+    T: WriterCommit,
+{
+    pub fn new(indexer: Box<dyn Indexer<T, U>>) -> Self {
+        Self {
+            bus_manager: RwLock::new(None),
+            current_search: Arc::new(Mutex::new(None)),
+            indexer,
+            settings: Box::new(SearchSettingsRepositoryMemory::new()),
+            synonyms: Box::new(SynonymRepositoryMemory::new()),
+            saved_searches: Box::new(SavedSearchRepositoryMemory::new()),
+            history: Box::new(SearchHistoryRepositoryMemory::new()),
+            smart_collections: Box::new(SmartCollectionRepositoryMemory::new()),
+            smart_contents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_settings_repository(mut self, settings: Box<dyn SearchSettingsRepository>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    pub fn with_synonym_repository(mut self, synonyms: Box<dyn SynonymRepository>) -> Self {
+        self.synonyms = synonyms;
+        self.indexer.apply_synonyms(self.synonyms.list());
+        self
+    }
+
+    pub fn with_saved_search_repositories(
+        mut self,
+        saved_searches: Box<dyn SavedSearchRepository>,
+        history: Box<dyn SearchHistoryRepository>,
+    ) -> Self {
+        self.saved_searches = saved_searches;
+        self.history = history;
+        self
+    }
+
+    pub fn with_smart_collection_repository(
+        mut self,
+        smart_collections: Box<dyn SmartCollectionRepository>,
+    ) -> Self {
+        self.smart_collections = smart_collections;
+        self
+    }
+
+    pub fn synonyms(&self) -> Vec<SynonymGroup> {
+        self.synonyms.list()
+    }
+
+    /// Queries containing one of `terms` also match the others from now on.
+    pub fn add_synonyms(&self, terms: Vec<String>) -> Result<SynonymGroup, AppError> {
+        let group = SynonymGroup::new(terms)?;
+        self.synonyms.add(group.clone())?;
+        self.indexer.apply_synonyms(self.synonyms.list());
+        Ok(group)
+    }
+
+    pub fn remove_synonyms(&self, id: &Uuid) -> Result<(), AppError> {
+        self.synonyms.delete(id)?;
+        self.indexer.apply_synonyms(self.synonyms.list());
+        Ok(())
+    }
+
+    /// Past searches, most recent first.
+    pub fn search_history(&self) -> Vec<SearchHistoryEntry> {
+        self.history.list()
+    }
+
+    pub fn saved_searches(&self) -> Vec<SavedSearch> {
+        self.saved_searches.list()
+    }
+
+    pub fn save_search(&self, name: &str, request: SearchRequest) -> Result<SavedSearch, AppError> {
+        let saved_search = SavedSearch::new(name, request)?;
+        self.saved_searches.add(saved_search.clone())?;
+        Ok(saved_search)
+    }
+
+    pub fn rename_saved_search(&self, id: &Uuid, name: &str) -> Result<SavedSearch, AppError> {
+        let mut saved_search = self
+            .saved_searches
+            .get_by_id(id)
+            .ok_or(AppError::not_found("Saved search", id))?;
+        saved_search.rename(name)?;
+        self.saved_searches.add(saved_search.clone())?;
+        Ok(saved_search)
+    }
+
+    pub fn delete_saved_search(&self, id: &Uuid) -> Result<(), AppError> {
+        self.saved_searches.delete(id)
+    }
+
+    pub fn run_saved_search(
+        &self,
+        id: &Uuid,
+        stream: bool,
+        allow_path: Option<&ApplyPathRights>,
+    ) -> Result<SearchPage, AppError> {
+        let saved_search = self
+            .saved_searches
+            .get_by_id(id)
+            .ok_or(AppError::not_found("Saved search", id))?;
+        self.search(&saved_search.request, stream, allow_path)
+    }
+
+    /// Every smart collection along with the videos currently matching it.
+    pub fn smart_collections(&self) -> Result<Vec<(SmartCollection, Vec<Video>)>, AppError> {
+        self.smart_collections
+            .list()
+            .into_iter()
+            .map(|collection| {
+                let videos = self.smart_videos(&collection)?;
+                Ok((collection, videos))
+            })
+            .collect()
+    }
+
+    pub fn create_smart_collection(
+        &self,
+        title: &str,
+        request: SearchRequest,
+    ) -> Result<(SmartCollection, Vec<Video>), AppError> {
+        let collection = SmartCollection::new(title, request)?;
+        let videos = self.smart_videos(&collection)?;
+        self.smart_collections.add(collection.clone())?;
+        self.smart_contents
+            .lock()
+            .insert(collection.id, videos.iter().map(|v| v.id).collect());
+        Ok((collection, videos))
+    }
+
+    pub fn update_smart_collection(
+        &self,
+        id: &Uuid,
+        title: &str,
+        request: SearchRequest,
+    ) -> Result<(SmartCollection, Vec<Video>), AppError> {
+        let mut collection = self
+            .smart_collections
+            .get_by_id(id)
+            .ok_or(AppError::not_found("Smart collection", id))?;
+        collection.update(title, request)?;
+        let videos = self.smart_videos(&collection)?;
+        self.smart_collections.add(collection.clone())?;
+        self.smart_contents
+            .lock()
+            .insert(collection.id, videos.iter().map(|v| v.id).collect());
+        self.publish(
+            "collection:updated",
+            CollectionUpdated::from(&collection.to_collection(videos.clone())),
+        );
+        Ok((collection, videos))
+    }
+
+    pub fn delete_smart_collection(&self, id: &Uuid) -> Result<(), AppError> {
+        self.smart_collections.delete(id)?;
+        self.smart_contents.lock().remove(id);
+        self.publish(
+            "collection:deleted",
+            CollectionDeleted { collection_id: *id },
+        );
+        Ok(())
+    }
+
+    /// Re-evaluates the smart collections and publishes `collection:updated`
+    /// for those whose videos changed.
+    fn refresh_smart_collections(&self) {
+        for collection in self.smart_collections.list() {
+            let videos = match self.smart_videos(&collection) {
+                Ok(videos) => videos,
+                Err(e) => {
+                    log::error!("Failed to evaluate smart collection {}: {e}", collection.id);
+                    continue;
+                }
+            };
+            let ids: Vec<Uuid> = videos.iter().map(|v| v.id).collect();
+            let previous = self
+                .smart_contents
+                .lock()
+                .insert(collection.id, ids.clone());
+            // The first evaluation after startup only records the contents.
+            if previous.is_some_and(|previous| previous != ids) {
+                self.publish(
+                    "collection:updated",
+                    CollectionUpdated::from(&collection.to_collection(videos)),
+                );
+            }
+        }
+    }
+
+    fn smart_videos(&self, collection: &SmartCollection) -> Result<Vec<Video>, AppError> {
+        let mut videos = Vec::new();
+        loop {
+            let request = SearchRequest {
+                group_by_video: true,
+                offset: videos.len(),
+                limit: Some(MAX_PAGE_SIZE),
+                ..collection.request.clone()
+            };
+            let page = self.indexer.search(&request, Arc::new(|| false))?;
+            let last_page = page.hits.is_empty() || videos.len() + page.hits.len() >= page.total;
+            videos.extend(page.hits.into_iter().map(|hit| hit.video));
+            if last_page {
+                return Ok(videos);
+            }
+        }
+    }
+
+    pub fn settings(&self) -> SearchSettings {
+        self.settings.get()
+    }
+
+    /// Saves `settings` and rebuilds the index when the analyzer changed.
+    pub fn update_settings(&self, settings: SearchSettings) -> Result<(), AppError> {
+        settings.boosts.validate()?;
+        self.settings.save(&settings)?;
+        self.indexer.apply_settings(&settings)
+    }
+
+    pub fn initialize(&self, bus_manager: EventBusManager) {
+        *self.bus_manager.write() = Some(bus_manager);
+    }
+
+    pub fn index_all_videos(&self) -> Result<(), AppError> {
+        self.indexer.index_all_videos()?;
+        self.refresh_smart_collections();
+        Ok(())
+    }
+
+    pub fn rebuild_required(&self) -> bool {
+        self.indexer.rebuild_required()
+    }
+
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Result<Suggestions, AppError> {
+        self.indexer.suggest(prefix, limit)
+    }
+
+    pub fn similar_videos(
+        &self,
+        video_id: &Uuid,
+        limit: usize,
+        allow_path: Option<&ApplyPathRights>,
+    ) -> Result<Vec<SearchHit>, AppError> {
+        let hits = self.indexer.similar(video_id, limit)?;
+        if let Some(callback) = allow_path {
+            for hit in &hits {
+                callback(&hit.video.path)?;
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Keeps the index in sync with the collection events published by a
+    /// command in one commit, then re-evaluates the smart collections once.
+    /// The services publish once the collections are saved, so hits read the
+    /// saved videos.
+    pub fn handle_events(&self, events: &[Event]) -> Result<(), AppError> {
+        let mut changes = Vec::new();
+        for event in events {
+            changes.extend(index_change(event)?);
+        }
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let result = self.indexer.apply_changes(&changes);
+        self.refresh_smart_collections();
+        result
+    }
+
+    /// Returns the ranked hits for `request`. In `stream` mode each hit is
+    /// also published as a `video:selected` event.
+    pub fn search(
+        &self,
+        request: &SearchRequest,
+        stream: bool,
+        allow_path: Option<&ApplyPathRights>,
+    ) -> Result<SearchPage, AppError> {
+        let search_id = self.start_search(request);
+        self.run_search(search_id, request, stream, allow_path)
+    }
+
+    /// Remembers the first page of a submitted, non-blank search. A failure
+    /// to persist the history is only logged.
+    pub fn record_search(&self, request: &SearchRequest) {
+        if request.query.trim().is_empty() {
+            return;
+        }
+        let entry = SearchHistoryEntry {
+            request: SearchRequest {
+                offset: 0,
+                ..request.clone()
+            },
+            searched_at: clock().now(),
+        };
+        if let Err(e) = self.history.add(entry) {
+            log::error!("Failed to record search history: {e}");
+        }
+    }
+
+    /// Makes `search_id` the current search, superseding the running ones.
+    fn start_search(&self, request: &SearchRequest) -> Uuid {
+        let search_id = Uuid::new_v4();
+        *self.current_search.lock() = Some(search_id);
+        self.publish(
+            "search:started",
+            SearchStarted {
+                search_id,
+                query: request.query.clone(),
+            },
+        );
+        search_id
+    }
+
+    fn run_search(
+        &self,
+        search_id: Uuid,
+        request: &SearchRequest,
+        stream: bool,
+        allow_path: Option<&ApplyPathRights>,
+    ) -> Result<SearchPage, AppError> {
+        self.ensure_current(search_id)?;
+        let current_search = self.current_search.clone();
+        let is_cancelled: IsCancelled = Arc::new(move || *current_search.lock() != Some(search_id));
+        let mut page = self.indexer.search(request, is_cancelled)?;
+        page.search_id = Some(search_id);
+        for hit in &page.hits {
+            if let Some(callback) = allow_path {
+                callback(&hit.video.path)?;
+            }
+        }
+        self.ensure_current(search_id)?;
+        if stream {
+            for hit in &page.hits {
+                self.ensure_current(search_id)?;
+                self.publish(
+                    "video:selected",
+                    VideoSelected {
+                        search_id,
+                        video: &hit.video,
+                    },
+                );
+            }
+        }
+        self.publish(
+            "search:completed",
+            SearchCompleted {
+                search_id,
+                total: page.total,
+                cancelled: false,
+            },
+        );
+        Ok(page)
+    }
+
+    /// Fails once a newer search started, telling the frontend to drop this one.
+    fn ensure_current(&self, search_id: Uuid) -> Result<(), AppError> {
+        if *self.current_search.lock() == Some(search_id) {
+            return Ok(());
+        }
+        self.publish(
+            "search:completed",
+            SearchCompleted {
+                search_id,
+                total: 0,
+                cancelled: true,
+            },
+        );
+        Err(AppError::cancelled("Search", search_id))
+    }
+
+    fn publish<D: serde::Serialize>(&self, event_type: &str, data: D) {
+        if let Some(bus_manager) = &*self.bus_manager.read() {
+            bus_manager.publish(event_type, data);
+        }
+    }
+}
+
+fn index_change(event: &Event) -> Result<Option<IndexChange>, AppError> {
+    let change = match event.event_type.as_str() {
+        "video:added" | "video:updated" => IndexChange::VideoUpdated {
+            video: serde_json::from_value(event.data.clone()).map_err(AppError::index)?,
+            collection_id: event_uuid(event, "collection_id")?,
+        },
+        "video:removed" => IndexChange::VideoRemoved {
+            video_id: event_uuid(event, "video_id")?,
+            collection_id: event_uuid(event, "collection_id")?,
+        },
+        "collection:updated" => {
+            let collection_id = event_uuid(event, "collection_id")?;
+            match repositories().collections().get_by_id(&collection_id) {
+                Some(collection) => IndexChange::CollectionUpdated(collection),
+                None => return Ok(None),
+            }
+        }
+        "collection:deleted" => IndexChange::CollectionRemoved(event_uuid(event, "collection_id")?),
+        _ => return Ok(None),
+    };
+    Ok(Some(change))
+}
+
+fn event_uuid(event: &Event, key: &str) -> Result<Uuid, AppError> {
+    event
+        .data
+        .get(key)
+        .and_then(|value| value.as_str())
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .ok_or(AppError::index(format!(
+            "{} event has no valid {key}",
+            event.event_type
+        )))
+}
+
+#[cfg(test)]
+mod search_service_setup {
+    use crate::collections::collections::CollectionRepositoryMemory;
+    use crate::collections::trash::TrashRepositoryMemory;
+    use crate::infra::event_bus::memory_event_bus::MemoryEventBus;
+    use crate::repositories::{with_test_repositories, Repositories, RepositoriesGuard};
+    use std::sync::Arc;
+
+    pub fn setup() -> (Arc<MemoryEventBus>, RepositoriesGuard) {
+        let mem = CollectionRepositoryMemory::new();
+        let _repos_guard = with_test_repositories(Repositories::new(
+            Arc::new(mem),
+            Arc::new(TrashRepositoryMemory::new()),
+        ));
+        let event_bus = Arc::new(MemoryEventBus::new());
+
+        (event_bus, _repos_guard)
+    }
+}
+
+#[cfg(test)]
+mod search_service_test {
+    use crate::collections::collection_builder::{a_collection, a_video};
+    use crate::collections::collections::{Collection, CollectionService, Style, Video};
+    use crate::collections::events::{VideoAddedToCollection, VideoRemovedFromCollection};
+    use crate::error::AppError;
+    use crate::event_bus::{Event, EventBusManager};
+    use crate::infra::event_bus::indexing_event_bus::IndexingEventBus;
+    use crate::repositories::repositories;
+    use crate::search::search_request::{
+        FacetFilters, RangeFilter, SearchFilters, SearchMode, SearchRequest, SortBy, SortOrder,
+    };
+    use crate::search::search_results::{
+        FacetCount, HitCollection, SearchHit, SearchPage, Suggestions, VideoSelected,
+    };
+    use crate::search::search_service::search_service_setup::setup;
+    use crate::search::search_service::{
+        Fields, IndexChange, Indexer, IsCancelled, SearchService, TantivyIndexer, WriterCommit,
+    };
+    use crate::search::search_settings::{AnalyzerSettings, FieldBoosts, SearchSettings};
+    use chrono::{TimeZone, Utc};
+    use parking_lot::Mutex;
+    use serde_json::json;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct DummyWriter {
+        commits: usize,
+    }
+    impl WriterCommit for DummyWriter {
+        fn commit_all(&mut self) -> Result<(), AppError> {
+            self.commits += 1;
+            Ok(())
+        }
+    }
+
+    struct DummyIndexer {
+        pub indexed_videos: Arc<Mutex<Vec<(Uuid, Video)>>>,
+        pub writer: Mutex<DummyWriter>,
+    }
+
+    impl Indexer<DummyWriter, String> for DummyIndexer {
+        fn initialize() -> Box<dyn Indexer<DummyWriter, String>>
+        where
+            Self: Sized,
+        {
+            Box::new(DummyIndexer {
+                indexed_videos: Arc::new(Mutex::new(vec![])),
+                writer: Mutex::new(DummyWriter::default()),
+            })
+        }
+
+        fn retrieve_writer(
+            &self,
+        ) -> crate::search::search_service::Writer<'_, DummyWriter, String> {
+            crate::search::search_service::Writer {
+                writer: self.writer.lock(),
+                fields: Fields {
+                    name: "name".to_string(),
+                    artist: "artist".to_string(),
+                    song: "song".to_string(),
+                    style: "style".to_string(),
+                    tags: "tags".to_string(),
+                    id: "id".to_string(),
+                    path: "path".to_string(),
+                    collection_id: "collection_id".to_string(),
+                    collection_title: "collection_title".to_string(),
+                    style_facet: "style_facet".to_string(),
+                    artist_facet: "artist_facet".to_string(),
+                    tags_facet: "tags_facet".to_string(),
+                    collection_facet: "collection_facet".to_string(),
+                    song_facet: "song_facet".to_string(),
+                    artist_sort: "artist_sort".to_string(),
+                    song_sort: "song_sort".to_string(),
+                    duration_seconds: "duration_seconds".to_string(),
+                    size_bytes: "size_bytes".to_string(),
+                    added_at: "added_at".to_string(),
+                },
+            }
+        }
+
+        fn index_video(
+            &self,
+            _video: &Video,
+            _collection_id: &Uuid,
+            _collection_title: &str,
+            _writer: &crate::search::search_service::Writer<'_, DummyWriter, String>,
+        ) {
+            let mut indexed_videos = self.indexed_videos.lock();
+            indexed_videos.push((*_collection_id, _video.clone()));
+        }
+
+        fn delete_video(
+            &self,
+            _video_id: &Uuid,
+            _collection_id: Option<&Uuid>,
+            _writer: &crate::search::search_service::Writer<'_, DummyWriter, String>,
+        ) {
+            self.indexed_videos.lock().retain(|(collection_id, video)| {
+                video.id != *_video_id || _collection_id.is_some_and(|c| c != collection_id)
+            });
+        }
+
+        fn delete_collection(
+            &self,
+            _collection_id: &Uuid,
+            _writer: &crate::search::search_service::Writer<'_, DummyWriter, String>,
+        ) {
+            self.indexed_videos
+                .lock()
+                .retain(|(collection_id, _)| collection_id != _collection_id);
+        }
+
+        fn delete_all_videos(
+            &self,
+            _writer: &crate::search::search_service::Writer<'_, DummyWriter, String>,
+        ) {
+            self.indexed_videos.lock().clear();
+        }
+
+        fn search(
+            &self,
+            _request: &SearchRequest,
+            _is_cancelled: IsCancelled,
+        ) -> Result<SearchPage, AppError> {
+            todo!()
+        }
+
+        fn suggest(&self, _prefix: &str, _limit: usize) -> Result<Suggestions, AppError> {
+            Ok(Suggestions::default())
+        }
+
+        fn similar(&self, _video_id: &Uuid, _limit: usize) -> Result<Vec<SearchHit>, AppError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn index_all_videos() {
+        let (_event_bus, _repositories_guard) = setup();
+        let indexed_videos = Arc::new(Mutex::new(vec![]));
+        let indexer = Box::new(DummyIndexer {
+            indexed_videos: indexed_videos.clone(),
+            writer: Mutex::new(DummyWriter::default()),
+        });
+        let search_service = SearchService::new(indexer);
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .with_title("Collection 1")
+                    .add_video(&a_video().build())
+                    .add_video(&a_video().build())
+                    .build(),
+            )
+            .unwrap();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .with_title("Collection 2")
+                    .add_video(&a_video().build())
+                    .build(),
+            )
+            .unwrap();
+
+        search_service.index_all_videos().unwrap();
+
+        assert_eq!(indexed_videos.lock().len(), 3);
+    }
+
+    #[test]
+    fn keeps_index_in_sync_with_collection_events() {
+        let (_event_bus, _repositories_guard) = setup();
+        let indexed_videos = Arc::new(Mutex::new(vec![]));
+        let search_service = SearchService::new(Box::new(DummyIndexer {
+            indexed_videos: indexed_videos.clone(),
+            writer: Mutex::new(DummyWriter::default()),
+        }));
+        let collection_id = Uuid::new_v4();
+        let video = a_video().build();
+        let event = |event_type: &str, data: serde_json::Value| Event {
+            event_type: event_type.to_string(),
+            data,
+        };
+
+        search_service
+            .handle_events(&[event(
+                "video:added",
+                serde_json::to_value(VideoAddedToCollection::from((&video, collection_id)))
+                    .unwrap(),
+            )])
+            .unwrap();
+        assert_eq!(*indexed_videos.lock(), vec![(collection_id, video.clone())]);
+
+        search_service
+            .handle_events(&[event(
+                "video:removed",
+                serde_json::to_value(VideoRemovedFromCollection::from((&video, collection_id)))
+                    .unwrap(),
+            )])
+            .unwrap();
+        assert!(indexed_videos.lock().is_empty());
+    }
+
+    #[test]
+    fn indexes_the_events_of_a_command_in_one_commit() {
+        let (event_bus, _repositories_guard) = setup();
+        let indexed_videos = Arc::new(Mutex::new(vec![]));
+        let search_service = Box::leak(Box::new(SearchService::new(Box::new(DummyIndexer {
+            indexed_videos: indexed_videos.clone(),
+            writer: Mutex::new(DummyWriter::default()),
+        }))));
+        let bus_manager = EventBusManager::new(Arc::new(IndexingEventBus::new(
+            event_bus.clone(),
+            search_service,
+        )));
+        let collection_id = Uuid::new_v4();
+
+        for _ in 0..3 {
+            bus_manager.publish(
+                "video:added",
+                VideoAddedToCollection::from((&a_video().build(), collection_id)),
+            );
+        }
+        assert!(event_bus.events.lock().is_empty());
+        drop(bus_manager);
+
+        assert_eq!(indexed_videos.lock().len(), 3);
+        assert_eq!(search_service.indexer.retrieve_writer().writer.commits, 1);
+        assert_eq!(event_bus.events.lock().len(), 3);
+    }
+
+    #[test]
+    fn returns_ranked_hits_with_highlights() {
+        let (_event_bus, _repositories_guard) = setup();
+        let interpol = a_video().with_artist("Interpol").build();
+        let tagged = a_video()
+            .with_tags(vec!["interpol", "solo", "live", "rehearsal"])
+            .build();
+        let collection = a_collection()
+            .add_video(&interpol)
+            .add_video(&tagged)
+            .add_video(&a_video().build())
+            .build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let page = search_service
+            .search(&SearchRequest::new("interpol"), false, None)
+            .unwrap();
+
+        assert_eq!(page.hits.len(), 2);
+        assert!(page.hits[0].score >= page.hits[1].score);
+        let hit = page
+            .hits
+            .iter()
+            .find(|h| h.video.id == interpol.id)
+            .unwrap();
+        assert_eq!(
+            hit.collections,
+            vec![HitCollection {
+                id: collection.id,
+                title: collection.title.clone()
+            }]
+        );
+        assert_eq!(hit.highlights[0].field, "artist");
+        assert_eq!(hit.highlights[0].fragment, "Interpol");
+        assert_eq!(hit.highlights[0].highlighted, vec![(0, 8)]);
+    }
+
+    #[test]
+    fn counts_each_video_once_per_facet_value() {
+        let (_event_bus, _repositories_guard) = setup();
+        let shared = a_video()
+            .with_artist("Muse")
+            .with_style(vec![Style::Blues])
+            .build();
+        for title in ["Lessons", "Favourites"] {
+            repositories()
+                .collections()
+                .add(a_collection().with_title(title).add_video(&shared).build())
+                .unwrap();
+        }
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let page = search_service
+            .search(&SearchRequest::new("muse"), false, None)
+            .unwrap();
+
+        assert_eq!(page.total, 2);
+        assert_eq!(
+            page.facets.style,
+            vec![FacetCount {
+                value: "Blues".to_string(),
+                count: 1,
+            }]
+        );
+        assert_eq!(page.facets.collection.len(), 2);
+        assert!(page.facets.collection.iter().all(|c| c.count == 1));
+    }
+
+    #[test]
+    fn counts_and_filters_facets() {
+        let (_event_bus, _repositories_guard) = setup();
+        let blues_solo = a_video()
+            .with_style(vec![Style::Blues])
+            .with_tags(vec!["lesson", "solo"])
+            .build();
+        let blues_warmup = a_video()
+            .with_style(vec![Style::Blues])
+            .with_tags(vec!["lesson", "warmup"])
+            .build();
+        let rock_solo = a_video()
+            .with_style(vec![Style::HardRock])
+            .with_tags(vec!["lesson", "solo"])
+            .build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&blues_solo)
+                    .add_video(&blues_warmup)
+                    .add_video(&rock_solo)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let page = search_service
+            .search(&SearchRequest::new("lesson"), false, None)
+            .unwrap();
+        assert_eq!(
+            page.facets.style,
+            vec![
+                FacetCount {
+                    value: "Blues".to_string(),
+                    count: 2
+                },
+                FacetCount {
+                    value: "Hard Rock".to_string(),
+                    count: 1
+                },
+            ]
+        );
+
+        let request = SearchRequest {
+            query: "lesson".to_string(),
+            facets: FacetFilters {
+                style: vec!["Blues".to_string()],
+                tags: vec!["solo".to_string()],
+                ..FacetFilters::default()
+            },
+            ..SearchRequest::default()
+        };
+        let page = search_service.search(&request, false, None).unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].video.id, blues_solo.id);
+        assert_eq!(page.facets.tags[0].count, 1);
+    }
+
+    #[test]
+    fn fuzzy_mode_tolerates_typos_and_ranks_exact_matches_first() {
+        let (_event_bus, _repositories_guard) = setup();
+        let interpol = a_video().with_artist("Interpol").build();
+        let typo = a_video().with_artist("Interpal").build();
+        let hendrix = a_video().with_artist("Hendrix").build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&typo)
+                    .add_video(&interpol)
+                    .add_video(&hendrix)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let fuzzy = |query: &str| SearchRequest {
+            mode: SearchMode::Fuzzy,
+            ..SearchRequest::new(query)
+        };
+
+        let exact_page = search_service
+            .search(&SearchRequest::new("hendrx"), false, None)
+            .unwrap();
+        assert!(exact_page.hits.is_empty());
+        let page = search_service
+            .search(&fuzzy("hendrx"), false, None)
+            .unwrap();
+        assert_eq!(page.hits[0].video.id, hendrix.id);
+
+        let page = search_service
+            .search(&fuzzy("interpol"), false, None)
+            .unwrap();
+        let ids: Vec<_> = page.hits.iter().map(|hit| hit.video.id).collect();
+        assert_eq!(ids, vec![interpol.id, typo.id]);
+    }
+
+    #[test]
+    fn rebuilds_index_when_analyzer_settings_change() {
+        let (_event_bus, _repositories_guard) = setup();
+        let video = a_video().with_song("Déjà Vu").build();
+        repositories()
+            .collections()
+            .add(a_collection().add_video(&video).build())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        let without_folding = SearchSettings {
+            analyzer: AnalyzerSettings {
+                ascii_folding: false,
+                ..AnalyzerSettings::default()
+            },
+            ..SearchSettings::default()
+        };
+
+        search_service.update_settings(without_folding).unwrap();
+        let page = search_service
+            .search(&SearchRequest::new("deja"), false, None)
+            .unwrap();
+        assert!(page.hits.is_empty());
+
+        search_service
+            .update_settings(SearchSettings::default())
+            .unwrap();
+        let page = search_service
+            .search(&SearchRequest::new("deja"), false, None)
+            .unwrap();
+        assert_eq!(page.hits[0].video.id, video.id);
+        assert_eq!(search_service.settings(), SearchSettings::default());
+    }
+
+    #[test]
+    fn suggests_values_by_field_ranked_by_frequency() {
+        let (_event_bus, _repositories_guard) = setup();
+        let video = |artist: &str, song: &str| a_video().with_artist(artist).with_song(song);
+        let mut collection = a_collection();
+        for _ in 0..2 {
+            collection = collection.add_video(&video("Interpol", "Evil").build());
+        }
+        collection = collection
+            .add_video(&video("Intervals", "Ghost").build())
+            .add_video(&video("", "").with_tags(vec!["intro", "solo"]).build())
+            .add_video(&video("", "Évidence intérieure").build());
+        repositories()
+            .collections()
+            .add(collection.build())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let suggestions = search_service.suggest("INT", 5).unwrap();
+
+        let values = |counts: &[FacetCount]| {
+            counts
+                .iter()
+                .map(|c| (c.value.clone(), c.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            values(&suggestions.artist),
+            vec![("Interpol".to_string(), 2), ("Intervals".to_string(), 1)]
+        );
+        assert_eq!(values(&suggestions.tags), vec![("intro".to_string(), 1)]);
+        assert_eq!(
+            values(&suggestions.song),
+            vec![("Évidence intérieure".to_string(), 1)]
+        );
+        assert!(suggestions.style.is_empty());
+        assert_eq!(
+            search_service.suggest(" ", 5).unwrap(),
+            Suggestions::default()
+        );
+    }
+
+    #[test]
+    fn filters_by_ranges_even_without_query() {
+        let (_event_bus, _repositories_guard) = setup();
+        let last_month = Utc.with_ymd_and_hms(2025, 12, 15, 12, 0, 0).unwrap();
+        let short_blues = a_video()
+            .with_style(vec![Style::Blues])
+            .with_duration_seconds(150)
+            .with_size_bytes(1_000)
+            .with_added_at(last_month)
+            .build();
+        let long_blues = a_video()
+            .with_style(vec![Style::Blues])
+            .with_duration_seconds(600)
+            .with_added_at(last_month)
+            .build();
+        let old_short_blues = a_video()
+            .with_style(vec![Style::Blues])
+            .with_duration_seconds(120)
+            .with_added_at(Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap())
+            .build();
+        let collection = a_collection()
+            .add_video(&short_blues)
+            .add_video(&long_blues)
+            .add_video(&old_short_blues)
+            .build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        repositories()
+            .collections()
+            .add(a_collection().add_video(&short_blues).build())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let request = SearchRequest {
+            filters: SearchFilters {
+                duration_seconds: Some(RangeFilter {
+                    min: None,
+                    max: Some(180),
+                }),
+                added_at: Some(RangeFilter {
+                    min: Some(Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap()),
+                    max: Some(Utc.with_ymd_and_hms(2025, 12, 31, 23, 59, 59).unwrap()),
+                }),
+                collection_id: Some(collection.id),
+                ..SearchFilters::default()
+            },
+            ..SearchRequest::new("")
+        };
+        let page = search_service.search(&request, false, None).unwrap();
+
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].video.id, short_blues.id);
+        assert_eq!(page.hits[0].collections[0].id, collection.id);
+    }
+
+    #[test]
+    fn searches_style_display_names_with_field_syntax() {
+        let (_event_bus, _repositories_guard) = setup();
+        let hard_rock_solo = a_video()
+            .with_artist("Interpol")
+            .with_style(vec![Style::HardRock])
+            .with_tags(vec!["solo"])
+            .build();
+        let rock_solo = a_video()
+            .with_artist("Interpol")
+            .with_style(vec![Style::Rock])
+            .with_tags(vec!["solo"])
+            .build();
+        let folk = a_video().with_style(vec![Style::CountryFolk]).build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&hard_rock_solo)
+                    .add_video(&rock_solo)
+                    .add_video(&folk)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let search = |query: &str| {
+            search_service
+                .search(&SearchRequest::new(query), false, None)
+                .map(|page| page.hits.iter().map(|hit| hit.video.id).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            search(r#"artist:interpol style:"hard rock" tag:solo"#).unwrap(),
+            vec![hard_rock_solo.id]
+        );
+        assert_eq!(search("hardrock").unwrap(), vec![hard_rock_solo.id]);
+        assert_eq!(search("folk").unwrap(), vec![folk.id]);
+        assert_eq!(
+            search("artist:interpol -style:hard").unwrap(),
+            vec![rock_solo.id]
+        );
+        assert_eq!(search("artist:interpol colour:red").unwrap().len(), 2);
+        assert_eq!(
+            search("artist:interpol style:"),
+            Err(AppError::invalid_query("style:", 16, "Missing value"))
+        );
+    }
+
+    #[test]
+    fn ranks_fields_by_configurable_boosts() {
+        let (_event_bus, _repositories_guard) = setup();
+        let tagged = a_video()
+            .with_artist("Muse")
+            .with_song("Madness")
+            .with_tags(vec!["interpol"])
+            .build();
+        let by_artist = a_video().with_artist("Interpol").with_song("Evil").build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&tagged)
+                    .add_video(&by_artist)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let ranked_ids = || {
+            search_service
+                .search(&SearchRequest::new("interpol"), false, None)
+                .unwrap()
+                .hits
+                .iter()
+                .map(|hit| hit.video.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranked_ids(), vec![by_artist.id, tagged.id]);
+
+        search_service
+            .update_settings(SearchSettings {
+                boosts: FieldBoosts {
+                    tags: 10.0,
+                    ..FieldBoosts::default()
+                },
+                ..SearchSettings::default()
+            })
+            .unwrap();
+        assert_eq!(ranked_ids(), vec![tagged.id, by_artist.id]);
+        assert_eq!(
+            search_service.update_settings(SearchSettings {
+                boosts: FieldBoosts {
+                    artist: -1.0,
+                    ..FieldBoosts::default()
+                },
+                ..SearchSettings::default()
+            }),
+            Err(AppError::invalid_input(
+                "Field boosts must be non-negative numbers"
+            ))
+        );
+    }
+
+    #[test]
+    fn expands_queries_with_synonyms() {
+        let (_event_bus, _repositories_guard) = setup();
+        let distorted = a_video()
+            .with_artist("Muse")
+            .with_song("Hysteria")
+            .with_tags(vec!["distortion"])
+            .build();
+        let acdc = a_video()
+            .with_artist("AC/DC")
+            .with_song("Thunderstruck")
+            .with_tags(vec!["rythmique"])
+            .build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&distorted)
+                    .add_video(&acdc)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let search = |query: &str| {
+            search_service
+                .search(&SearchRequest::new(query), false, None)
+                .unwrap()
+                .hits
+                .iter()
+                .map(|hit| hit.video.id)
+                .collect::<Vec<_>>()
+        };
+        assert!(search("disto").is_empty());
+
+        let disto = search_service
+            .add_synonyms(vec!["disto".to_string(), "Distortion".to_string()])
+            .unwrap();
+        search_service
+            .add_synonyms(vec!["AC/DC".to_string(), "ACDC".to_string()])
+            .unwrap();
+
+        assert_eq!(search("disto"), vec![distorted.id]);
+        assert_eq!(search("artist:acdc"), vec![acdc.id]);
+        assert_eq!(search_service.synonyms().len(), 2);
+        assert_eq!(
+            search_service.add_synonyms(vec!["solo".to_string(), " SOLO ".to_string()]),
+            Err(AppError::invalid_input(
+                "A synonym group needs at least two different terms"
+            ))
+        );
+
+        search_service.remove_synonyms(&disto.id).unwrap();
+        assert!(search("disto").is_empty());
+    }
+
+    #[test]
+    fn stops_ranking_cancelled_searches() {
+        let (_event_bus, _repositories_guard) = setup();
+        repositories()
+            .collections()
+            .add(a_collection().add_video(&a_video().build()).build())
+            .unwrap();
+        let indexer = TantivyIndexer::initialize();
+        indexer.index_all_videos().unwrap();
+
+        let page = indexer
+            .search(&SearchRequest::new(""), Arc::new(|| true))
+            .unwrap();
+
+        assert!(page.hits.is_empty());
+        assert_eq!(page.total, 0);
+    }
+
+    #[test]
+    fn records_history_and_reruns_saved_searches() {
+        let (_event_bus, _repositories_guard) = setup();
+        let video = a_video().with_artist("Muse").with_song("Hysteria").build();
+        repositories()
+            .collections()
+            .add(a_collection().add_video(&video).build())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        for query in ["muse", "", "hysteria", "muse"] {
+            search_service.record_search(&SearchRequest {
+                offset: 10,
+                ..SearchRequest::new(query)
+            });
+        }
+        search_service
+            .search(&SearchRequest::new("hyst"), false, None)
+            .unwrap();
+
+        let history = search_service.search_history();
+        assert_eq!(
+            history.iter().map(|e| &e.request).collect::<Vec<_>>(),
+            vec![&SearchRequest::new("muse"), &SearchRequest::new("hysteria")]
+        );
+
+        let saved = search_service
+            .save_search("Muse", SearchRequest::new("artist:muse"))
+            .unwrap();
+        let renamed = search_service
+            .rename_saved_search(&saved.id, " Muse songs ")
+            .unwrap();
+        assert_eq!(renamed.name, "Muse songs");
+        assert_eq!(search_service.saved_searches(), vec![renamed]);
+        let page = search_service
+            .run_saved_search(&saved.id, false, None)
+            .unwrap();
+        assert_eq!(page.hits[0].video.id, video.id);
+        assert_eq!(
+            search_service.rename_saved_search(&saved.id, "  "),
+            Err(AppError::invalid_input("A saved search needs a name"))
+        );
+
+        search_service.delete_saved_search(&saved.id).unwrap();
+        assert!(search_service.saved_searches().is_empty());
+        assert_eq!(
+            search_service.run_saved_search(&saved.id, false, None),
+            Err(AppError::not_found("Saved search", saved.id))
+        );
+    }
+
+    #[test]
+    fn reevaluates_smart_collections_when_the_library_changes() {
+        let (event_bus, _repositories_guard) = setup();
+        let blues_solo = |duration_seconds: u64| {
+            a_video()
+                .with_style(vec![Style::Blues])
+                .with_tags(vec!["solo"])
+                .with_duration_seconds(duration_seconds)
+                .build()
+        };
+        let short_solo = blues_solo(120);
+        let mut collection = a_collection().add_video(&short_solo).build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.initialize(EventBusManager::new(event_bus.clone()));
+        search_service.index_all_videos().unwrap();
+        let request = SearchRequest {
+            filters: SearchFilters {
+                duration_seconds: Some(RangeFilter {
+                    min: None,
+                    max: Some(300),
+                }),
+                ..SearchFilters::default()
+            },
+            ..SearchRequest::new("style:blues tag:solo")
+        };
+        let (smart, videos) = search_service
+            .create_smart_collection("Short blues solos", request)
+            .unwrap();
+        assert_eq!(videos, vec![short_solo.clone()]);
+
+        let add = |video: &Video, collection: &mut Collection| {
+            collection.add_video(video.clone());
+            repositories()
+                .collections()
+                .add(collection.clone())
+                .unwrap();
+            search_service
+                .handle_events(&[Event {
+                    event_type: "video:added".to_string(),
+                    data: serde_json::to_value(VideoAddedToCollection::from((
+                        video,
+                        collection.id,
+                    )))
+                    .unwrap(),
+                }])
+                .unwrap();
+        };
+        add(&blues_solo(600), &mut collection);
+        assert!(event_bus.events.lock().is_empty());
+        let other_solo = blues_solo(200);
+        add(&other_solo, &mut collection);
+
+        let events = event_bus.events.lock().clone();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "collection:updated");
+        assert_eq!(events[0].data["collection_id"], smart.id.to_string());
+        let (_, videos) = &search_service.smart_collections().unwrap()[0];
+        let mut ids: Vec<Uuid> = videos.iter().map(|v| v.id).collect();
+        ids.sort();
+        let mut expected = vec![short_solo.id, other_solo.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn updates_smart_collections_with_created_collections() {
+        let (event_bus, _repositories_guard) = setup();
+        let search_service = Box::leak(Box::new(SearchService::new(TantivyIndexer::initialize())));
+        search_service.initialize(EventBusManager::new(event_bus.clone()));
+        let (smart, videos) = search_service
+            .create_smart_collection("Solos", SearchRequest::new("solo"))
+            .unwrap();
+        assert!(videos.is_empty());
+        let bus_manager = EventBusManager::new(Arc::new(IndexingEventBus::new(
+            event_bus.clone(),
+            search_service,
+        )));
+        let videos = serde_json::from_value(json!([{
+            "path": "lessons/solo.mp4",
+            "thumbnail": "",
+            "size_bytes": 100,
+            "duration_seconds": 60
+        }]))
+        .unwrap();
+
+        let collection = CollectionService::create_collection(videos, bus_manager).unwrap();
+
+        let (_, videos) = &search_service.smart_collections().unwrap()[0];
+        assert_eq!(videos, &collection.videos);
+        let events = event_bus.events.lock();
+        let smart_updates: Vec<_> = events
+            .iter()
+            .filter(|e| e.event_type == "collection:updated")
+            .collect();
+        assert_eq!(smart_updates.len(), 1);
+        assert_eq!(smart_updates[0].data["collection_id"], smart.id.to_string());
+    }
+
+    #[test]
+    fn finds_similar_videos_without_source_and_duplicates() {
+        let (_event_bus, _repositories_guard) = setup();
+        let video = |name: &str, artist: &str, style: Style, tags: Vec<&str>| {
+            a_video()
+                .with_name(name)
+                .with_artist(artist)
+                .with_song(name)
+                .with_style(vec![style])
+                .with_tags(tags)
+                .build()
+        };
+        let source = video("Obstacle", "Interpol", Style::Rock, vec!["solo", "live"]);
+        let same_artist = video("Evil", "Interpol", Style::Rock, vec!["solo"]);
+        let same_style = video("Hysteria", "Muse", Style::Rock, vec![]);
+        let unrelated = video("Take Five", "Brubeck", Style::Jazz, vec!["sax"]);
+        let duplicate = Video {
+            id: Uuid::new_v4(),
+            ..source.clone()
+        };
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&source)
+                    .add_video(&same_artist)
+                    .add_video(&same_style)
+                    .add_video(&unrelated)
+                    .add_video(&duplicate)
+                    .build(),
+            )
+            .unwrap();
+        // Copies of a file in other collections get their own ids.
+        for _ in 0..5 {
+            let copy = |video: &Video| Video {
+                id: Uuid::new_v4(),
+                ..video.clone()
+            };
+            repositories()
+                .collections()
+                .add(
+                    a_collection()
+                        .add_video(&copy(&source))
+                        .add_video(&copy(&same_artist))
+                        .build(),
+                )
+                .unwrap();
+        }
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+
+        let similar = search_service.similar_videos(&source.id, 2, None).unwrap();
+
+        assert_eq!(
+            similar
+                .iter()
+                .map(|hit| &hit.video.path)
+                .collect::<Vec<_>>(),
+            vec![&same_artist.path, &same_style.path]
+        );
+        assert_eq!(similar[0].collections.len(), 6);
+        assert_eq!(
+            search_service
+                .similar_videos(&source.id, usize::MAX, None)
+                .unwrap()
+                .len(),
+            2
+        );
+        let unknown = Uuid::new_v4();
+        assert_eq!(
+            search_service.similar_videos(&unknown, 10, None),
+            Err(AppError::not_found("Video", unknown))
+        );
+    }
+
+    #[test]
+    fn drops_results_of_superseded_searches() {
+        let (event_bus, _repositories_guard) = setup();
+        let interpol = a_video().with_artist("Interpol").with_song("Evil").build();
+        let muse = a_video().with_artist("Muse").with_song("Hysteria").build();
+        repositories()
+            .collections()
+            .add(a_collection().add_video(&interpol).add_video(&muse).build())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.initialize(EventBusManager::new(event_bus.clone()));
+        search_service.index_all_videos().unwrap();
+        let older_request = SearchRequest::new("interpol");
+        let newer_request = SearchRequest::new("muse");
+
+        let older = search_service.start_search(&older_request);
+        let newer = search_service.start_search(&newer_request);
+        let older_result = search_service.run_search(older, &older_request, true, None);
+        let newer_page = search_service
+            .run_search(newer, &newer_request, true, None)
+            .unwrap();
+
+        assert_eq!(older_result, Err(AppError::cancelled("Search", older)));
+        assert_eq!(newer_page.search_id, Some(newer));
+        let events: Vec<_> = event_bus
+            .events
+            .lock()
+            .iter()
+            .map(|e| (e.event_type.clone(), e.data.clone()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    "search:started".to_string(),
+                    json!({"search_id": older, "query": "interpol"})
+                ),
+                (
+                    "search:started".to_string(),
+                    json!({"search_id": newer, "query": "muse"})
+                ),
+                (
+                    "search:completed".to_string(),
+                    json!({"search_id": older, "total": 0, "cancelled": true})
+                ),
+                (
+                    "video:selected".to_string(),
+                    serde_json::to_value(VideoSelected {
+                        search_id: newer,
+                        video: &muse
+                    })
+                    .unwrap()
+                ),
+                (
+                    "search:completed".to_string(),
+                    json!({"search_id": newer, "total": 1, "cancelled": false})
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn returns_collection_entries_or_groups_them_by_video() {
+        let (_event_bus, _repositories_guard) = setup();
+        let shared = a_video().with_artist("Interpol").build();
+        let other = a_video().with_artist("Interpol").build();
+        let lessons = a_collection()
+            .with_title("Lessons")
+            .add_video(&shared)
+            .add_video(&other)
+            .build();
+        let shared_copy = Video {
+            id: Uuid::new_v4(),
+            ..shared.clone()
+        };
+        let favourites = a_collection()
+            .with_title("Favourites")
+            .add_video(&shared_copy)
+            .build();
+        repositories().collections().add(lessons.clone()).unwrap();
+        repositories()
+            .collections()
+            .add(favourites.clone())
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let entries = |page: SearchPage| {
+            let mut entries: Vec<_> = page
+                .hits
+                .into_iter()
+                .map(|hit| {
+                    let mut titles: Vec<_> = hit.collections.into_iter().map(|c| c.title).collect();
+                    titles.sort();
+                    (hit.video.path, titles)
+                })
+                .collect();
+            entries.sort();
+            (entries, page.total)
+        };
+        let titles = |titles: &[&str]| titles.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        let mut expected_grouped = vec![
+            (shared.path.clone(), titles(&["Favourites", "Lessons"])),
+            (other.path.clone(), titles(&["Lessons"])),
+        ];
+        expected_grouped.sort();
+
+        let per_collection = search_service
+            .search(&SearchRequest::new("interpol"), false, None)
+            .unwrap();
+        let grouped = search_service
+            .search(
+                &SearchRequest {
+                    group_by_video: true,
+                    ..SearchRequest::new("interpol")
+                },
+                false,
+                None,
+            )
+            .unwrap();
+        let in_favourites = search_service
+            .search(&SearchRequest::new("collection:favourites"), false, None)
+            .unwrap();
+
+        assert_eq!(per_collection.total, 3);
+        assert_eq!(entries(grouped), (expected_grouped, 2));
+        assert_eq!(
+            entries(in_favourites),
+            (vec![(shared.path, titles(&["Favourites"]))], 1)
+        );
+    }
+
+    #[test]
+    fn indexes_created_collections_with_their_title() {
+        let (event_bus, _repositories_guard) = setup();
+        let search_service = Box::leak(Box::new(SearchService::new(TantivyIndexer::initialize())));
+        let bus_manager =
+            EventBusManager::new(Arc::new(IndexingEventBus::new(event_bus, search_service)));
+        let videos = serde_json::from_value(json!([{
+            "path": "lessons/solo.mp4",
+            "thumbnail": "",
+            "size_bytes": 100,
+            "duration_seconds": 60
+        }]))
+        .unwrap();
+
+        let collection = CollectionService::create_collection(videos, bus_manager).unwrap();
+
+        let page = search_service
+            .search(
+                &SearchRequest::new(&format!("collection:\"{}\"", collection.title)),
+                false,
+                None,
+            )
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(
+            page.hits[0].collections,
+            vec![HitCollection {
+                id: collection.id,
+                title: collection.title.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn sorts_and_paginates_hits() {
+        let (_event_bus, _repositories_guard) = setup();
+        let zebra = a_video()
+            .with_artist("Zebra")
+            .with_duration_seconds(300)
+            .with_added_at(Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap())
+            .build();
+        let alpha = a_video()
+            .with_artist("alpha")
+            .with_duration_seconds(120)
+            .with_added_at(Utc.with_ymd_and_hms(2026, 1, 20, 12, 0, 0).unwrap())
+            .build();
+        let emile = a_video()
+            .with_artist("Émile")
+            .with_duration_seconds(200)
+            .with_added_at(Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap())
+            .build();
+        let unknown = a_video().with_artist("").with_duration_seconds(60).build();
+        repositories()
+            .collections()
+            .add(
+                a_collection()
+                    .add_video(&zebra)
+                    .add_video(&alpha)
+                    .add_video(&emile)
+                    .add_video(&unknown)
+                    .build(),
+            )
+            .unwrap();
+        let search_service = SearchService::new(TantivyIndexer::initialize());
+        search_service.index_all_videos().unwrap();
+        let sorted_ids = |request: SearchRequest| {
+            let page = search_service.search(&request, false, None).unwrap();
+            let ids: Vec<_> = page.hits.iter().map(|hit| hit.video.id).collect();
+            (ids, page.total)
+        };
+
+        assert_eq!(
+            sorted_ids(SearchRequest {
+                sort: SortBy::Artist,
+                ..SearchRequest::new("")
+            }),
+            (vec![alpha.id, emile.id, zebra.id, unknown.id], 4)
+        );
+        assert_eq!(
+            sorted_ids(SearchRequest {
+                sort: SortBy::Artist,
+                offset: 1,
+                limit: Some(2),
+                ..SearchRequest::new("")
+            }),
+            (vec![emile.id, zebra.id], 4)
+        );
+        assert_eq!(
+            sorted_ids(SearchRequest {
+                sort: SortBy::Duration,
+                order: Some(SortOrder::Descending),
+                limit: Some(2),
+                ..SearchRequest::new("")
+            }),
+            (vec![zebra.id, emile.id], 4)
+        );
+        assert_eq!(
+            sorted_ids(SearchRequest {
+                sort: SortBy::AddedAt,
+                ..SearchRequest::new("")
+            }),
+            (vec![alpha.id, zebra.id, emile.id, unknown.id], 4)
+        );
+        for group_by_video in [false, true] {
+            assert_eq!(
+                sorted_ids(SearchRequest {
+                    offset: usize::MAX,
+                    limit: Some(usize::MAX),
+                    group_by_video,
+                    ..SearchRequest::new("")
+                }),
+                (vec![], 4)
+            );
+        }
+    }
+
+    #[test]
+    fn reopens_persisted_index_without_rebuild() {
+        let (event_bus, _repositories_guard) = setup();
+        let index_dir = std::env::temp_dir().join(format!("alexandria-{}", Uuid::new_v4()));
+        let video = a_video().build();
+        let collection = a_collection().add_video(&video).build();
+        repositories()
+            .collections()
+            .add(collection.clone())
+            .unwrap();
+
+        let indexer = TantivyIndexer::open(&index_dir, &SearchSettings::default()).unwrap();
+        assert!(indexer.rebuild_required());
+        indexer
+            .apply_changes(&[IndexChange::VideoUpdated {
+                video: video.clone(),
+                collection_id: collection.id,
+            }])
+            .unwrap();
+        drop(indexer);
+
+        let search_service = SearchService::new(
+            TantivyIndexer::open(&index_dir, &SearchSettings::default()).unwrap(),
+        );
+        search_service.initialize(EventBusManager::new(event_bus.clone()));
+        assert!(!search_service.rebuild_required());
+        search_service
+            .search(
+                &SearchRequest::new(&format!("\"{}\"", video.artist)),
+                true,
+                None,
+            )
+            .unwrap();
+
+        let events = event_bus.events.lock();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].event_type, "video:selected");
+        assert_eq!(events[1].data["id"], video.id.to_string());
+        std::fs::remove_dir_all(&index_dir).unwrap();
     }
 }
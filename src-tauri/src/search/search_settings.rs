@@ -0,0 +1,162 @@
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{read_json_or_recover, write_json_atomically};
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use tantivy::tokenizer::{
+    AsciiFoldingFilter, Language, LowerCaser, SimpleTokenizer, Stemmer, StopWordFilter,
+    TextAnalyzer,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchSettings {
+    #[serde(default)]
+    pub analyzer: AnalyzerSettings,
+    #[serde(default)]
+    pub boosts: FieldBoosts,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AnalyzerSettings {
+    /// Language used for stemming and stop words, `None` disables stemming.
+    #[serde(default)]
+    pub language: Option<Language>,
+    /// Folds accents so that "deja vu" matches "Déjà Vu".
+    #[serde(default)]
+    pub ascii_folding: bool,
+    #[serde(default)]
+    pub remove_stop_words: bool,
+}
+
+impl Default for AnalyzerSettings {
+    fn default() -> Self {
+        Self {
+            language: Some(Language::French),
+            ascii_folding: true,
+            remove_stop_words: false,
+        }
+    }
+}
+
+impl AnalyzerSettings {
+    pub fn text_analyzer(&self) -> TextAnalyzer {
+        let mut builder =
+            TextAnalyzer::builder(SimpleTokenizer::default()).filter_dynamic(LowerCaser);
+        if self.ascii_folding {
+            builder = builder.filter_dynamic(AsciiFoldingFilter);
+        }
+        if let Some(language) = self.language {
+            if self.remove_stop_words {
+                if let Some(stop_words) = StopWordFilter::new(language) {
+                    builder = builder.filter_dynamic(stop_words);
+                }
+            }
+            builder = builder.filter_dynamic(Stemmer::new(language));
+        }
+        builder.build()
+    }
+}
+
+/// Weight of a match in each text field. Changing them needs no rebuild.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct FieldBoosts {
+    pub name: f32,
+    pub artist: f32,
+    pub song: f32,
+    pub style: f32,
+    pub tags: f32,
+}
+
+impl Default for FieldBoosts {
+    fn default() -> Self {
+        Self {
+            name: 1.5,
+            artist: 3.0,
+            song: 3.0,
+            style: 1.5,
+            tags: 1.0,
+        }
+    }
+}
+
+impl FieldBoosts {
+    pub fn for_field(&self, field_name: &str) -> f32 {
+        match field_name {
+            "name" => self.name,
+            "artist" => self.artist,
+            "song" => self.song,
+            "style" => self.style,
+            "tags" => self.tags,
+            _ => 1.0,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let boosts = [self.name, self.artist, self.song, self.style, self.tags];
+        if boosts
+            .iter()
+            .all(|boost| boost.is_finite() && *boost >= 0.0)
+        {
+            Ok(())
+        } else {
+            Err(AppError::invalid_input(
+                "Field boosts must be non-negative numbers",
+            ))
+        }
+    }
+}
+
+pub trait SearchSettingsRepository: Send + Sync {
+    fn get(&self) -> SearchSettings;
+    fn save(&self, settings: &SearchSettings) -> Result<(), AppError>;
+}
+
+pub struct SearchSettingsRepositoryFile {
+    path: PathBuf,
+}
+
+impl SearchSettingsRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            path: base_dir.join("search-settings.json"),
+        }
+    }
+}
+
+impl SearchSettingsRepository for SearchSettingsRepositoryFile {
+    fn get(&self) -> SearchSettings {
+        if !self.path.exists() {
+            return SearchSettings::default();
+        }
+        read_json_or_recover(&self.path).unwrap_or_else(|e| {
+            log::error!("Failed to read search settings: {e}");
+            SearchSettings::default()
+        })
+    }
+
+    fn save(&self, settings: &SearchSettings) -> Result<(), AppError> {
+        write_json_atomically(&self.path, settings)
+    }
+}
+
+#[derive(Default)]
+pub struct SearchSettingsRepositoryMemory {
+    settings: Mutex<SearchSettings>,
+}
+
+impl SearchSettingsRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SearchSettingsRepository for SearchSettingsRepositoryMemory {
+    fn get(&self) -> SearchSettings {
+        self.settings.lock().clone()
+    }
+
+    fn save(&self, settings: &SearchSettings) -> Result<(), AppError> {
+        *self.settings.lock() = settings.clone();
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::clock::clock;
+use crate::collections::collections::{Collection, Video};
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{read_json_or_recover, write_json_atomically};
+use crate::search::search_request::SearchRequest;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A collection holding the videos that match `request`, re-evaluated
+/// against the search index whenever the library changes.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SmartCollection {
+    pub id: Uuid,
+    pub title: String,
+    pub request: SearchRequest,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SmartCollection {
+    pub fn new(title: &str, request: SearchRequest) -> Result<Self, AppError> {
+        let now = clock().now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            title: Self::valid_title(title)?,
+            request,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update(&mut self, title: &str, request: SearchRequest) -> Result<(), AppError> {
+        self.title = Self::valid_title(title)?;
+        self.request = request;
+        self.updated_at = clock().now();
+        Ok(())
+    }
+
+    /// The collection shown to users, holding the current matches.
+    pub fn to_collection(&self, videos: Vec<Video>) -> Collection {
+        Collection {
+            id: self.id,
+            title: self.title.clone(),
+            description: None,
+            cover: None,
+            created_at: Some(self.created_at),
+            updated_at: Some(self.updated_at),
+            videos,
+        }
+    }
+
+    fn valid_title(title: &str) -> Result<String, AppError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(AppError::invalid_input("A smart collection needs a title"));
+        }
+        Ok(title.to_string())
+    }
+}
+
+pub trait SmartCollectionRepository: Send + Sync {
+    fn list(&self) -> Vec<SmartCollection>;
+    /// Adds `collection` or replaces the one with the same id.
+    fn add(&self, collection: SmartCollection) -> Result<(), AppError>;
+    fn delete(&self, id: &Uuid) -> Result<(), AppError>;
+    fn get_by_id(&self, id: &Uuid) -> Option<SmartCollection> {
+        self.list().into_iter().find(|c| c.id == *id)
+    }
+}
+
+fn upsert(collections: &mut Vec<SmartCollection>, collection: SmartCollection) {
+    match collections.iter_mut().find(|c| c.id == collection.id) {
+        Some(existing) => *existing = collection,
+        None => collections.push(collection),
+    }
+}
+
+fn remove(collections: &mut Vec<SmartCollection>, id: &Uuid) -> Result<(), AppError> {
+    let count = collections.len();
+    collections.retain(|c| c.id != *id);
+    if collections.len() == count {
+        return Err(AppError::not_found("Smart collection", id));
+    }
+    Ok(())
+}
+
+pub struct SmartCollectionRepositoryFile {
+    path: PathBuf,
+}
+
+impl SmartCollectionRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            path: base_dir.join("smart-collections.json"),
+        }
+    }
+}
+
+impl SmartCollectionRepository for SmartCollectionRepositoryFile {
+    fn list(&self) -> Vec<SmartCollection> {
+        if !self.path.exists() {
+            return Vec::new();
+        }
+        read_json_or_recover(&self.path).unwrap_or_else(|e| {
+            log::error!("Failed to read smart collections: {e}");
+            Vec::new()
+        })
+    }
+
+    fn add(&self, collection: SmartCollection) -> Result<(), AppError> {
+        let mut collections = self.list();
+        upsert(&mut collections, collection);
+        write_json_atomically(&self.path, &collections)
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let mut collections = self.list();
+        remove(&mut collections, id)?;
+        write_json_atomically(&self.path, &collections)
+    }
+}
+
+#[derive(Default)]
+pub struct SmartCollectionRepositoryMemory {
+    collections: Mutex<Vec<SmartCollection>>,
+}
+
+impl SmartCollectionRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SmartCollectionRepository for SmartCollectionRepositoryMemory {
+    fn list(&self) -> Vec<SmartCollection> {
+        self.collections.lock().clone()
+    }
+
+    fn add(&self, collection: SmartCollection) -> Result<(), AppError> {
+        upsert(&mut self.collections.lock(), collection);
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        remove(&mut self.collections.lock(), id)
+    }
+}
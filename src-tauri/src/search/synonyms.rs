@@ -0,0 +1,117 @@
+use crate::error::AppError;
+use crate::infra::files::atomic_file::{read_json_or_recover, write_json_atomically};
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Terms searched interchangeably, such as "disto" and "distortion".
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SynonymGroup {
+    pub id: Uuid,
+    pub terms: Vec<String>,
+}
+
+impl SynonymGroup {
+    pub fn new(terms: Vec<String>) -> Result<Self, AppError> {
+        let mut distinct: Vec<String> = Vec::new();
+        for term in terms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !distinct
+                .iter()
+                .any(|d| d.to_lowercase() == term.to_lowercase())
+            {
+                distinct.push(term.to_string());
+            }
+        }
+        if distinct.len() < 2 {
+            return Err(AppError::invalid_input(
+                "A synonym group needs at least two different terms",
+            ));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            terms: distinct,
+        })
+    }
+}
+
+pub trait SynonymRepository: Send + Sync {
+    fn list(&self) -> Vec<SynonymGroup>;
+    fn add(&self, group: SynonymGroup) -> Result<(), AppError>;
+    fn delete(&self, id: &Uuid) -> Result<(), AppError>;
+}
+
+pub struct SynonymRepositoryFile {
+    path: PathBuf,
+}
+
+impl SynonymRepositoryFile {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            path: base_dir.join("synonyms.json"),
+        }
+    }
+
+    fn save(&self, groups: &[SynonymGroup]) -> Result<(), AppError> {
+        write_json_atomically(&self.path, &groups)
+    }
+}
+
+impl SynonymRepository for SynonymRepositoryFile {
+    fn list(&self) -> Vec<SynonymGroup> {
+        if !self.path.exists() {
+            return Vec::new();
+        }
+        read_json_or_recover(&self.path).unwrap_or_else(|e| {
+            log::error!("Failed to read synonyms: {e}");
+            Vec::new()
+        })
+    }
+
+    fn add(&self, group: SynonymGroup) -> Result<(), AppError> {
+        let mut groups = self.list();
+        groups.push(group);
+        self.save(&groups)
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let mut groups = self.list();
+        let count = groups.len();
+        groups.retain(|group| group.id != *id);
+        if groups.len() == count {
+            return Err(AppError::not_found("Synonym group", id));
+        }
+        self.save(&groups)
+    }
+}
+
+#[derive(Default)]
+pub struct SynonymRepositoryMemory {
+    groups: Mutex<Vec<SynonymGroup>>,
+}
+
+impl SynonymRepositoryMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SynonymRepository for SynonymRepositoryMemory {
+    fn list(&self) -> Vec<SynonymGroup> {
+        self.groups.lock().clone()
+    }
+
+    fn add(&self, group: SynonymGroup) -> Result<(), AppError> {
+        self.groups.lock().push(group);
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<(), AppError> {
+        let mut groups = self.groups.lock();
+        let count = groups.len();
+        groups.retain(|group| group.id != *id);
+        if groups.len() == count {
+            return Err(AppError::not_found("Synonym group", id));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use crate::collections::collections::Video;
+use crate::collections::tauri_dtos::CollectionDTO;
+use crate::error::AppError;
+use crate::event_bus::{EventBus, EventBusManager};
+use crate::infra::event_bus::indexing_event_bus::IndexingEventBus;
+use crate::infra::event_bus::tauri_event_bus::TauriEventBus;
+use crate::infra::tauri::path::allow_path;
+use crate::search::saved_searches::{
+    SavedSearch, SavedSearchRepository, SearchHistoryEntry, SearchHistoryRepository,
+};
+use crate::search::search_request::SearchRequest;
+use crate::search::search_results::{SearchHit, SearchPage, Suggestions};
+use crate::search::search_service::{ApplyPathRights, Indexer, SearchService, TantivyIndexer};
+use crate::search::search_settings::{SearchSettings, SearchSettingsRepository};
+use crate::search::smart_collections::{SmartCollection, SmartCollectionRepository};
+use crate::search::synonyms::{SynonymGroup, SynonymRepository};
+use once_cell::sync::OnceCell;
+use std::path::Path;
+use std::sync::Arc;
+use tantivy::schema::Field;
+use tantivy::IndexWriter;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+static SEARCH_SERVICE: OnceCell<SearchService<IndexWriter, Field>> = OnceCell::new();
+
+pub fn init_search_service(
+    index_dir: &Path,
+    settings_repository: Box<dyn SearchSettingsRepository>,
+    synonym_repository: Box<dyn SynonymRepository>,
+    saved_search_repository: Box<dyn SavedSearchRepository>,
+    history_repository: Box<dyn SearchHistoryRepository>,
+    smart_collection_repository: Box<dyn SmartCollectionRepository>,
+    app: AppHandle,
+) -> Result<(), AppError> {
+    let settings = settings_repository.get();
+    let indexer = TantivyIndexer::open(index_dir, &settings).unwrap_or_else(|e| {
+        log::error!("Falling back to an in-memory search index: {e}");
+        TantivyIndexer::initialize()
+    });
+    indexer.apply_settings(&settings)?;
+    let search_service = SEARCH_SERVICE.get_or_init(|| {
+        SearchService::new(indexer)
+            .with_settings_repository(settings_repository)
+            .with_synonym_repository(synonym_repository)
+            .with_saved_search_repositories(saved_search_repository, history_repository)
+            .with_smart_collection_repository(smart_collection_repository)
+    });
+    search_service.initialize(EventBusManager::new(Arc::new(TauriEventBus::new(app))));
+    if search_service.rebuild_required() {
+        search_service.index_all_videos()?;
+    }
+    Ok(())
+}
+
+fn search_service() -> Result<&'static SearchService<IndexWriter, Field>, AppError> {
+    SEARCH_SERVICE
+        .get()
+        .ok_or(AppError::index("Search index is not initialized"))
+}
+
+/// Smart collections with their current videos, none while the search
+/// index is unavailable.
+pub fn smart_collections() -> Result<Vec<(SmartCollection, Vec<Video>)>, AppError> {
+    match SEARCH_SERVICE.get() {
+        Some(search_service) => search_service.smart_collections(),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Event bus for commands that change collections: the search index is
+/// updated before the event reaches the frontend.
+pub fn indexing_bus_manager(app: AppHandle) -> EventBusManager {
+    let tauri_bus: Arc<dyn EventBus> = Arc::new(TauriEventBus::new(app));
+    match SEARCH_SERVICE.get() {
+        Some(search_service) => {
+            EventBusManager::new(Arc::new(IndexingEventBus::new(tauri_bus, search_service)))
+        }
+        None => EventBusManager::new(tauri_bus),
+    }
+}
+
+#[tauri::command]
+pub async fn search_videos(
+    app: AppHandle,
+    request: SearchRequest,
+    stream: Option<bool>,
+    submitted: Option<bool>,
+) -> Result<SearchPage, AppError> {
+    let search_service = search_service()?;
+    if submitted.unwrap_or(false) {
+        search_service.record_search(&request);
+    }
+    let callback: ApplyPathRights = Box::new(move |path| allow_path(&app, path));
+    search_service.search(&request, stream.unwrap_or(false), Some(&callback))
+}
+
+#[tauri::command]
+pub async fn suggest_search_terms(
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Suggestions, AppError> {
+    search_service()?.suggest(&prefix, limit.unwrap_or(5))
+}
+
+#[tauri::command]
+pub async fn similar_videos(
+    app: AppHandle,
+    video_id: Uuid,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, AppError> {
+    let callback: ApplyPathRights = Box::new(move |path| allow_path(&app, path));
+    search_service()?.similar_videos(&video_id, limit.unwrap_or(10), Some(&callback))
+}
+
+#[tauri::command]
+pub async fn reindex_videos() -> Result<(), AppError> {
+    search_service()?.index_all_videos()
+}
+
+#[tauri::command]
+pub async fn get_search_settings() -> Result<SearchSettings, AppError> {
+    Ok(search_service()?.settings())
+}
+
+#[tauri::command]
+pub async fn update_search_settings(settings: SearchSettings) -> Result<(), AppError> {
+    search_service()?.update_settings(settings)
+}
+
+#[tauri::command]
+pub async fn get_synonyms() -> Result<Vec<SynonymGroup>, AppError> {
+    Ok(search_service()?.synonyms())
+}
+
+#[tauri::command]
+pub async fn add_synonyms(terms: Vec<String>) -> Result<SynonymGroup, AppError> {
+    search_service()?.add_synonyms(terms)
+}
+
+#[tauri::command]
+pub async fn remove_synonyms(id: Uuid) -> Result<(), AppError> {
+    search_service()?.remove_synonyms(&id)
+}
+
+#[tauri::command]
+pub async fn get_search_history() -> Result<Vec<SearchHistoryEntry>, AppError> {
+    Ok(search_service()?.search_history())
+}
+
+#[tauri::command]
+pub async fn get_saved_searches() -> Result<Vec<SavedSearch>, AppError> {
+    Ok(search_service()?.saved_searches())
+}
+
+#[tauri::command]
+pub async fn save_search(name: String, request: SearchRequest) -> Result<SavedSearch, AppError> {
+    search_service()?.save_search(&name, request)
+}
+
+#[tauri::command]
+pub async fn rename_saved_search(id: Uuid, name: String) -> Result<SavedSearch, AppError> {
+    search_service()?.rename_saved_search(&id, &name)
+}
+
+#[tauri::command]
+pub async fn delete_saved_search(id: Uuid) -> Result<(), AppError> {
+    search_service()?.delete_saved_search(&id)
+}
+
+#[tauri::command]
+pub async fn run_saved_search(
+    app: AppHandle,
+    id: Uuid,
+    stream: Option<bool>,
+) -> Result<SearchPage, AppError> {
+    let callback: ApplyPathRights = Box::new(move |path| allow_path(&app, path));
+    search_service()?.run_saved_search(&id, stream.unwrap_or(false), Some(&callback))
+}
+
+#[tauri::command]
+pub async fn create_smart_collection(
+    app: AppHandle,
+    title: String,
+    request: SearchRequest,
+) -> Result<CollectionDTO, AppError> {
+    let collection =
+        CollectionDTO::from(search_service()?.create_smart_collection(&title, request)?);
+    for video in &collection.collection.videos {
+        allow_path(&app, &video.path)?;
+    }
+    Ok(collection)
+}
+
+#[tauri::command]
+pub async fn update_smart_collection(
+    app: AppHandle,
+    id: Uuid,
+    title: String,
+    request: SearchRequest,
+) -> Result<CollectionDTO, AppError> {
+    let collection =
+        CollectionDTO::from(search_service()?.update_smart_collection(&id, &title, request)?);
+    for video in &collection.collection.videos {
+        allow_path(&app, &video.path)?;
+    }
+    Ok(collection)
+}
+
+#[tauri::command]
+pub async fn delete_smart_collection(id: Uuid) -> Result<(), AppError> {
+    search_service()?.delete_smart_collection(&id)
+}
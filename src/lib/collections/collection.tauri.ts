@@ -3,6 +3,7 @@ import type { Collection } from './collection';
 import { toVideo } from './video.tauri';
 
 export type VideoFromCollection = {
+  id: string;
   path: string;
   name: string;
   artist: string;
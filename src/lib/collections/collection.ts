@@ -4,4 +4,6 @@ export type Collection = {
   id: string;
   title: string;
   videos: Video[];
+  /** Smart collections hold the videos matching a saved query. */
+  smart?: boolean;
 };
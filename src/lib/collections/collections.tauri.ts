@@ -2,17 +2,21 @@ import type { Collection } from './collection';
 import { invoke } from '@tauri-apps/api/core';
 import { toVideo } from './video.tauri';
 import type { VideoFromCollection } from './collection.tauri';
+import type { SearchRequest } from '../search/search.tauri';
 
 type RetrievedCollection = {
   id: string;
   title: string;
   videos: VideoFromCollection[];
+  smart: boolean;
+  request: SearchRequest | null;
 };
 const toCollection = (collection: RetrievedCollection): Collection => {
   return {
     id: collection.id,
     title: collection.title,
     videos: collection.videos.map(toVideo),
+    smart: collection.smart,
   };
 };
 
@@ -10,6 +10,7 @@ export const toVideo = (
   const size = video.size_bytes || 0;
   const durationInSeconds = video.duration_seconds || 0;
   return {
+    id: video.id,
     path: video.path,
     name: video.name,
     artist: video.artist,
@@ -30,6 +31,7 @@ export const toVideo = (
 };
 export type VideoAddedToCollection = {
   collection_id: string;
+  id: string;
   path: string;
   name: string;
   artist: string;
@@ -39,6 +41,7 @@ export type VideoAddedToCollection = {
   thumbnail?: string;
   size_bytes?: number;
   duration_seconds?: number;
+  added_at?: string | null;
   error?: string | null;
 };
 
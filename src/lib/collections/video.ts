@@ -52,6 +52,7 @@ type Duration = HumanReadable & {
 };
 
 export type Video = {
+  id: string;
   path: string;
   thumbnail: string;
   size: Size;
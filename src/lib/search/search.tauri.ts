@@ -0,0 +1,110 @@
+import type { VideoFromCollection } from '../collections/collection.tauri';
+
+export type Highlight = {
+  field: string;
+  fragment: string;
+  highlighted: [number, number][];
+};
+
+export type HitCollection = {
+  id: string;
+  title: string;
+};
+
+export type SearchHit = {
+  video: VideoFromCollection;
+  collections: HitCollection[];
+  score: number;
+  highlights: Highlight[];
+};
+
+export type FacetCount = {
+  value: string;
+  count: number;
+};
+
+export type FacetCounts = {
+  style: FacetCount[];
+  artist: FacetCount[];
+  tags: FacetCount[];
+  collection: FacetCount[];
+};
+
+export type SearchPage = {
+  search_id: string | null;
+  hits: SearchHit[];
+  total: number;
+  facets: FacetCounts;
+};
+
+export type FacetFilters = {
+  style?: string[];
+  artist?: string[];
+  tags?: string[];
+  collection?: string[];
+};
+
+export type SearchMode = 'Exact' | 'Fuzzy';
+
+export type RangeFilter<T> = {
+  min?: T | null;
+  max?: T | null;
+};
+
+export type SearchFilters = {
+  duration_seconds?: RangeFilter<number> | null;
+  size_bytes?: RangeFilter<number> | null;
+  added_at?: RangeFilter<string> | null;
+  collection_id?: string | null;
+};
+
+export type SortBy = 'Relevance' | 'Artist' | 'Song' | 'Duration' | 'AddedAt';
+
+export type SortOrder = 'Ascending' | 'Descending';
+
+export type SearchRequest = {
+  query: string;
+  facets?: FacetFilters;
+  mode?: SearchMode;
+  filters?: SearchFilters;
+  sort?: SortBy;
+  order?: SortOrder | null;
+  offset?: number;
+  limit?: number | null;
+  group_by_video?: boolean;
+};
+
+export type Suggestions = {
+  artist: FacetCount[];
+  song: FacetCount[];
+  tags: FacetCount[];
+  style: FacetCount[];
+};
+
+export type SearchStarted = {
+  search_id: string;
+  query: string;
+};
+
+export type SearchCompleted = {
+  search_id: string;
+  total: number;
+  cancelled: boolean;
+};
+
+export type SynonymGroup = {
+  id: string;
+  terms: string[];
+};
+
+export type SavedSearch = {
+  id: string;
+  name: string;
+  request: SearchRequest;
+  created_at: string;
+};
+
+export type SearchHistoryEntry = {
+  request: SearchRequest;
+  searched_at: string;
+};
@@ -76,6 +76,7 @@ describe('Collection store', () => {
     const { addVideo } = get(selectedCollection);
     addVideo(
       aVideo()
+        .withId('1')
         .withPath('video.mp4')
         .withName('Video')
         .withArtist('Artist')
@@ -92,6 +93,7 @@ describe('Collection store', () => {
     expect(collections).toHaveLength(2);
     const collection = collections[1];
     expect(collection.videos[0]).toStrictEqual<Video>({
+      id: '1',
       path: 'video.mp4',
       thumbnail: 'thumbnail.jpg',
       size: { bytes: 0, toHumanReadable: expect.any(Function) },
@@ -31,6 +31,7 @@ class CollectionBuilder implements Builder<Collection> {
 }
 
 class VideoBuilder implements Builder<Video> {
+  private id: string = fakerFR.string.uuid();
   private path: string = fakerFR.system.filePath();
   private thumbnail: string = fakerFR.string.alpha();
   private size: number = fakerFR.number.int();
@@ -51,6 +52,11 @@ class VideoBuilder implements Builder<Video> {
     }
   }
 
+  withId(id: string) {
+    this.id = id;
+    return this;
+  }
+
   withName(name: string) {
     this.name = name;
     return this;
@@ -101,6 +107,7 @@ class VideoBuilder implements Builder<Video> {
       play(): string {
         return '';
       },
+      id: this.id,
       path: this.path,
       thumbnail: this.thumbnail,
       size: { bytes: this.size, toHumanReadable: () => '' },
@@ -5,6 +5,7 @@ describe('Tauri video', () => {
     const video = toVideo({
       artist: 'An artist',
       collection_id: '1',
+      id: '1',
       duration_seconds: 0,
       name: 'Video name',
       path: 'Video path',
@@ -22,6 +23,7 @@ describe('Tauri video', () => {
     const video = toVideo({
       artist: 'An artist',
       collection_id: '1',
+      id: '1',
       duration_seconds: 0,
       name: 'Video name',
       path: 'Video path',
@@ -39,6 +41,7 @@ describe('Tauri video', () => {
     const video = toVideo({
       artist: 'An artist',
       collection_id: '1',
+      id: '1',
       duration_seconds: 0,
       name: 'Video name',
       path: 'Video path',
@@ -56,6 +59,7 @@ describe('Tauri video', () => {
     const video = toVideo({
       artist: 'An artist',
       collection_id: '1',
+      id: '1',
       duration_seconds: 354,
       name: 'Video name',
       path: 'Video path',
@@ -73,6 +77,7 @@ describe('Tauri video', () => {
     const video = toVideo({
       artist: 'An artist',
       collection_id: '1',
+      id: '1',
       duration_seconds: 3661,
       name: 'Video name',
       path: 'Video path',